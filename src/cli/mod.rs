@@ -0,0 +1,412 @@
+use crate::{
+    remote::{Request, Response},
+    state::{
+        backend::{Backend, Sqlite},
+        lock::InstanceLock,
+        manual, paths, Content, DateTime, RunningSession, Session, Settings, SharedClock,
+        SpentTime, Topic,
+    },
+};
+use serde_json::Value;
+use std::{
+    error::Error,
+    io, thread,
+    time::{Duration, Instant},
+};
+
+static USAGE: &str = "\
+Usage: zeitig [COMMAND] [--create]
+
+Without a command, the tracker window is opened. While it is open, commands
+are handed to it, except for 'log' which reads the data file directly.
+
+Commands:
+    status                      Show the running session and today's total
+    start <action> <subject>    Start a session
+    stop                        Stop the running session
+    switch <action> <subject>   Stop the running session and start another one
+    log [YYYY-MM-DD]            List the sessions of a day, today by default
+    add <action> <subject> <start> <end|duration>
+                                Record a past session, with times given
+                                as 'HH:MM' or 'YYYY-MM-DD HH:MM' and
+                                durations as '1h30m', '45m' or '1:30'
+    help                        Show this message
+
+Options:
+    --create                    Create actions and subjects that do not exist yet
+";
+
+/// How long to wait for the lock to be released or the tracker to listen.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+const BUSY_RETRY: Duration = Duration::from_millis(50);
+
+/// Runs the command given on the command line and exits on failure.
+pub fn run(args: &[String]) {
+    if let Err(err) = execute(args) {
+        eprintln!("zeitig: {}", err);
+        std::process::exit(1);
+    }
+}
+
+/// Takes the lock for the tracker, or brings the window of the one started
+/// before to the front and returns `None`.
+pub fn lock_or_show() -> Option<InstanceLock> {
+    lock_or_forward(|| Ok(Request::Show)).unwrap_or_else(|err| {
+        eprintln!("zeitig: {}", err);
+        std::process::exit(1);
+    })
+}
+
+fn execute(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let create = args.iter().any(|arg| arg == "--create");
+    let args: Vec<&str> = args
+        .iter()
+        .map(String::as_str)
+        .filter(|arg| *arg != "--create")
+        .collect();
+
+    if matches!(args.as_slice(), ["help"] | ["--help"] | ["-h"]) {
+        print!("{}", USAGE);
+        return Ok(());
+    }
+
+    // Held while the data file is changed, reading it needs no lock.
+    let _lock = if matches!(args.as_slice(), ["log"] | ["log", _]) {
+        None
+    } else {
+        let lock = lock_or_forward(|| {
+            if create {
+                return Err("--create is not available while the tracker is running.".into());
+            }
+            request(&args)
+        })?;
+        match lock {
+            Some(lock) => Some(lock),
+            None => return Ok(()),
+        }
+    };
+
+    let mut cli = Cli::open(create)?;
+    match args.as_slice() {
+        ["status"] => cli.status(),
+        ["start", action, subject] => cli.start(action, subject),
+        ["stop"] => cli.stop(),
+        ["switch", action, subject] => {
+            cli.stop()?;
+            cli.start(action, subject)
+        }
+        ["log"] => cli.log(None),
+        ["log", day] => cli.log(Some(day)),
+        ["add", action, subject, started, ended] => cli.add(action, subject, started, ended),
+        _ => Err(format!("Invalid arguments.\n\n{}", USAGE).into()),
+    }
+}
+
+/// The request doing the same as the command given by `args`.
+fn request(args: &[&str]) -> Result<Request, Box<dyn Error>> {
+    let topic = |action: &str, subject: &str| (action.to_string(), subject.to_string());
+    Ok(match args {
+        ["status"] => Request::Status,
+        ["start", action, subject] => {
+            let (action, subject) = topic(action, subject);
+            Request::Start { action, subject }
+        }
+        ["stop"] => Request::Stop,
+        ["switch", action, subject] => {
+            let (action, subject) = topic(action, subject);
+            Request::Switch { action, subject }
+        }
+        ["add", action, subject, started, ended] => {
+            let (action, subject) = topic(action, subject);
+            Request::Add {
+                action,
+                subject,
+                started: started.to_string(),
+                ended: ended.to_string(),
+            }
+        }
+        _ => return Err(format!("Invalid arguments.\n\n{}", USAGE).into()),
+    })
+}
+
+/// Takes the lock, or hands the request to the tracker holding it and returns `None`.
+///
+/// A command run meanwhile or a tracker that is still starting holds the lock
+/// without listening for requests, so this is retried until it is released or
+/// the tracker listens.
+fn lock_or_forward(
+    request: impl Fn() -> Result<Request, Box<dyn Error>>,
+) -> Result<Option<InstanceLock>, Box<dyn Error>> {
+    let deadline = Instant::now() + BUSY_TIMEOUT;
+    loop {
+        if let Some(lock) = InstanceLock::acquire()? {
+            return Ok(Some(lock));
+        }
+        match forward(request()?) {
+            Err(err) if not_listening(&*err) && Instant::now() < deadline => {
+                thread::sleep(BUSY_RETRY)
+            }
+            result => return result.map(|()| None),
+        }
+    }
+}
+
+/// Whether `err` means that nothing listens for requests.
+fn not_listening(err: &(dyn Error + 'static)) -> bool {
+    err.downcast_ref::<io::Error>().map_or(false, |err| {
+        matches!(
+            err.kind(),
+            io::ErrorKind::NotFound | io::ErrorKind::ConnectionRefused
+        )
+    })
+}
+
+/// Hands `request` to the running tracker and prints the resulting status.
+#[cfg(unix)]
+fn forward(request: Request) -> Result<(), Box<dyn Error>> {
+    let status = match crate::remote::socket::send(&request)? {
+        Response::Ok(status) => status,
+        Response::Error(message) => return Err(message.into()),
+    };
+    if !status.is_null() {
+        print_status(&status);
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn forward(_request: Request) -> Result<(), Box<dyn Error>> {
+    Err("Zeitig is already running.".into())
+}
+
+/// Prints a status as answered by the tracker, see [`crate::remote::status`].
+fn print_status(status: &Value) {
+    let secs = |key: &str| SpentTime::from(Duration::from_secs(status[key].as_u64().unwrap_or(0)));
+    if status["running"].as_bool() == Some(true) {
+        let state = if status["paused"].as_bool() == Some(true) {
+            "Paused"
+        } else {
+            "Running"
+        };
+        let started = status["started"].as_str().unwrap_or_default();
+        println!(
+            "{}: {} {} since {} ({})",
+            state,
+            status["action"].as_str().unwrap_or_default(),
+            status["subject"].as_str().unwrap_or_default(),
+            started.get(11..16).unwrap_or(started),
+            secs("session_secs"),
+        );
+    } else {
+        println!("Not running.");
+    }
+    println!("Today: {}", secs("today_secs"));
+}
+
+struct Cli {
+    backend: Sqlite,
+    content: Content,
+    clock: SharedClock,
+    settings: Settings,
+    create: bool,
+}
+
+impl Cli {
+    fn open(create: bool) -> Result<Cli, Box<dyn Error>> {
+        let mut backend = Sqlite::new(paths::data_file())?;
+        backend.setup()?;
+        let content = backend.load_content()?;
+        Ok(Cli {
+            backend,
+            content,
+            clock: SharedClock::default(),
+            settings: Settings::load(),
+            create,
+        })
+    }
+
+    fn topic(&mut self, action: &str, subject: &str) -> Result<Topic, Box<dyn Error>> {
+        let action = match self.content.find_action_by_name(action) {
+            Some(action) => action,
+            None if self.create => {
+                let action = self.backend.create_action(action)?;
+                self.content.actions.insert_ord(action.clone());
+                println!("Created action {}.", action.as_ref());
+                action
+            }
+            None => {
+                return Err(format!(
+                    "There is no action named '{}', use --create to add it.",
+                    action
+                )
+                .into())
+            }
+        };
+        let subject = match self.content.find_subject_by_name(subject) {
+            Some(subject) => subject,
+            None if self.create => {
+                let subject = self.backend.create_subject(subject)?;
+                self.content.subjects.insert_ord(subject.clone());
+                println!("Created subject {}.", subject.as_ref());
+                subject
+            }
+            None => {
+                return Err(format!(
+                    "There is no subject named '{}', use --create to add it.",
+                    subject
+                )
+                .into())
+            }
+        };
+        Ok(Topic { action, subject })
+    }
+
+    fn commit(&mut self, session: &Session) -> Result<(), Box<dyn Error>> {
+        let time = self.content.time_table.get_mut(session.topic.clone());
+        *time += session.duration();
+        let total = *time;
+        self.backend.commit_session(session, &total)?;
+        Ok(())
+    }
+
+    fn status(&mut self) -> Result<(), Box<dyn Error>> {
+        let now = self.clock.now();
+        let today = now.start_of_day();
+        let mut total = self
+            .backend
+            .load_history(&self.content)?
+            .total_between(&today, &now);
+
+        match self.backend.load_running(&self.content)? {
+            Some(running) => {
+                let state = if running.paused.is_some() {
+                    "Paused"
+                } else {
+                    "Running"
+                };
+                let session = running.end(now);
+                println!(
+                    "{}: {} {} since {} ({})",
+                    state,
+                    session.topic.action.as_ref(),
+                    session.topic.subject.as_ref(),
+                    session.started.format("%H:%M"),
+                    session.duration(),
+                );
+                total += session.time_between(&today, &now);
+            }
+            None => println!("Not running."),
+        }
+        println!("Today: {}", total);
+        Ok(())
+    }
+
+    fn start(&mut self, action: &str, subject: &str) -> Result<(), Box<dyn Error>> {
+        if self.backend.load_running(&self.content)?.is_some() {
+            return Err("A session is already running, use switch to change it.".into());
+        }
+        let running = RunningSession {
+            topic: self.topic(action, subject)?,
+            started: self.clock.now(),
+            pauses: Default::default(),
+            paused: None,
+        };
+        self.backend.save_running(Some(&running))?;
+        println!(
+            "Started {} {} at {}.",
+            running.topic.action.as_ref(),
+            running.topic.subject.as_ref(),
+            running.started.format("%H:%M"),
+        );
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), Box<dyn Error>> {
+        let running = self
+            .backend
+            .load_running(&self.content)?
+            .ok_or("No session is running.")?;
+        let session = running.end(self.clock.now());
+        let duration = session.duration();
+        if *duration <= self.settings.discard_threshold() {
+            println!("Discarded the session, it only lasted {}.", duration);
+        } else {
+            let history = self.backend.load_history(&self.content)?;
+            for other in history.overlapping(&session) {
+                println!(
+                    "Warning: the session overlaps {} {} from {} to {}.",
+                    other.topic.action.as_ref(),
+                    other.topic.subject.as_ref(),
+                    other.started.format("%Y-%m-%d %H:%M"),
+                    other.ended.format("%H:%M"),
+                );
+            }
+            self.commit(&session)?;
+            println!(
+                "Stopped {} {} after {}.",
+                session.topic.action.as_ref(),
+                session.topic.subject.as_ref(),
+                duration,
+            );
+        }
+        self.backend.save_running(None)?;
+        Ok(())
+    }
+
+    fn log(&mut self, day: Option<&str>) -> Result<(), Box<dyn Error>> {
+        let now = self.clock.now();
+        let from = match day {
+            Some(day) => DateTime::parse(&format!("{} 00:00", day), &now)
+                .ok_or_else(|| format!("Invalid day '{}', expected YYYY-MM-DD.", day))?,
+            None => now.start_of_day(),
+        };
+        let to = from.after(SpentTime::from(Duration::from_secs(24 * 60 * 60)));
+
+        let history = self.backend.load_history(&self.content)?;
+        let mut sessions: Vec<&Session> = history
+            .iter()
+            .filter(|s| *s.started < *to && *s.ended > *from)
+            .collect();
+        sessions.sort_by_key(|s| *s.started);
+
+        for session in &sessions {
+            println!(
+                "{} - {}  {:>12}  {} {}",
+                session.started.format("%H:%M"),
+                session.ended.format("%H:%M"),
+                session.duration().to_string(),
+                session.topic.action.as_ref(),
+                session.topic.subject.as_ref(),
+            );
+        }
+        println!("Total: {}", history.total_between(&from, &to));
+        Ok(())
+    }
+
+    fn add(
+        &mut self,
+        action: &str,
+        subject: &str,
+        started: &str,
+        ended: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        let now = self.clock.now();
+        let started = DateTime::parse(started, &now)
+            .ok_or_else(|| format!("Invalid start '{}'.", started))?;
+        let ended = manual::parse_end(ended, &started)
+            .ok_or_else(|| format!("Invalid end or duration '{}'.", ended))?;
+        let session = Session {
+            topic: self.topic(action, subject)?,
+            started,
+            ended,
+            pauses: Default::default(),
+        };
+        let history = self.backend.load_history(&self.content)?;
+        let running = self.backend.load_running(&self.content)?;
+        let running_since = running.as_ref().map(|running| &running.started);
+        manual::validate(&session, &history, &now, running_since)?;
+        self.commit(&session)?;
+        println!("Added a session of {}.", session.duration());
+        Ok(())
+    }
+}
@@ -1,7 +1,6 @@
 use druid::{widget::Controller, Env, Event, EventCtx, Selector, TimerToken, Widget};
-use std::time::Duration;
 
-use crate::state::AppState;
+use crate::{controller::backend_msg, state::AppState};
 
 pub const SAVE_NOW: Selector = Selector::new("zeitig.save");
 
@@ -14,11 +13,9 @@ impl AutoSaver {
         Self { timer: None }
     }
 
-    fn save(&mut self, _data: &mut AppState) {
+    fn save(&mut self, ctx: &mut EventCtx, data: &mut AppState) {
         self.timer = None;
-        // TODO: actually save the data
-        // This will have to save the currently running session I guess.
-        //state::files::write_state(data.clone());
+        ctx.submit_command(backend_msg::SAVE_RUNNING.with(data.running()));
     }
 }
 
@@ -32,8 +29,8 @@ impl<W: Widget<AppState>> Controller<AppState, W> for AutoSaver {
         env: &Env,
     ) {
         match event {
-            Event::Command(cmd) if cmd.is(SAVE_NOW) => self.save(data),
-            Event::Timer(token) if Some(*token) == self.timer => self.save(data),
+            Event::Command(cmd) if cmd.is(SAVE_NOW) => self.save(ctx, data),
+            Event::Timer(token) if Some(*token) == self.timer => self.save(ctx, data),
             _ => (),
         }
         child.event(ctx, event, data, env)
@@ -48,7 +45,7 @@ impl<W: Widget<AppState>> Controller<AppState, W> for AutoSaver {
         env: &Env,
     ) {
         if self.timer.is_none() {
-            self.timer = Some(ctx.request_timer(Duration::from_secs(5)));
+            self.timer = Some(ctx.request_timer(data.settings.autosave_interval()));
         }
         child.update(ctx, old_data, data, env)
     }
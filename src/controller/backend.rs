@@ -1,23 +1,111 @@
-use crate::state::{
-    backend::{Backend, Sqlite},
-    paths, AppState, Session, SpentTime,
+use crate::{
+    i18n::tr,
+    state::{
+        backend::{Backend, Sqlite},
+        editor::SessionEdit,
+        outbox, paths, AppState, BackendError, Content, History, RunningSession, Session,
+        SpentTime, Topic,
+    },
 };
 use druid::{
-    widget::Controller, Env, Event, EventCtx, ExtEventSink, LifeCycle, LifeCycleCtx, Target, Widget,
+    widget::Controller, Env, Event, EventCtx, ExtEventSink, LifeCycle, LifeCycleCtx, Selector,
+    Target, Widget,
 };
 use std::{
+    collections::HashMap,
     error::Error,
-    sync::mpsc::{self, Sender},
+    mem,
+    sync::mpsc::{self, RecvTimeoutError, Sender},
     thread,
+    time::Duration,
 };
 
+/// How long the backend waits for commands before it checks for outside changes.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Clone)]
 enum BackendCommand {
     AddAction(String),
     AddSubject(String),
     AddSession(Session, SpentTime),
+    EditSessions(SessionEdit, Vec<(Topic, SpentTime)>),
+    SetPinned(Topic, bool),
+    SaveRunning(Option<RunningSession>),
+    /// Loads the content and the history again.
+    Reload,
     Stop,
 }
 
+impl BackendCommand {
+    /// What the command was supposed to do, to tell the user what failed.
+    fn describe(&self) -> String {
+        tr(match self {
+            BackendCommand::AddAction(_) => "error-add-action",
+            BackendCommand::AddSubject(_) => "error-add-subject",
+            BackendCommand::AddSession(..) => "error-add-session",
+            BackendCommand::EditSessions(..) => "error-edit-sessions",
+            BackendCommand::SetPinned(..) => "error-set-pinned",
+            BackendCommand::SaveRunning(_) => "error-save-running",
+            BackendCommand::Reload => "error-reload",
+            BackendCommand::Stop => "error-stop",
+        })
+    }
+
+    /// Whether a newer command of the same kind makes this one obsolete,
+    /// so that only the latest failure of that kind has to be kept.
+    fn supersedes_earlier(&self) -> bool {
+        matches!(
+            self,
+            BackendCommand::SaveRunning(_) | BackendCommand::Reload
+        )
+    }
+
+    /// Reloads change nothing, so they are left out when counting commands.
+    fn is_reload(&self) -> bool {
+        matches!(self, BackendCommand::Reload)
+    }
+
+    /// The command to send again on a retry, with the totals and the
+    /// running session taken from the current state.
+    fn refreshed(self, data: &AppState) -> BackendCommand {
+        match self {
+            BackendCommand::AddSession(session, _) => {
+                let total = data.content.time_table.get(&session.topic);
+                BackendCommand::AddSession(session, total)
+            }
+            BackendCommand::EditSessions(edit, totals) => {
+                let totals = totals
+                    .into_iter()
+                    .map(|(topic, _)| {
+                        let total = data.content.time_table.get(&topic);
+                        (topic, total)
+                    })
+                    .collect();
+                BackendCommand::EditSessions(edit, totals)
+            }
+            BackendCommand::SaveRunning(_) => BackendCommand::SaveRunning(data.running()),
+            command => command,
+        }
+    }
+}
+
+/// A command the backend could not execute, sent back to the UI.
+struct Failure {
+    message: String,
+    command: BackendCommand,
+}
+
+const FAILED: Selector<Failure> = Selector::new("zeitig.backend.failed");
+
+/// The data as loaded after the backend handled the given number of commands.
+struct Reloaded {
+    content: Content,
+    history: History,
+    handled: usize,
+}
+
+const RELOADED: Selector<Reloaded> = Selector::new("zeitig.backend.reloaded");
+
 #[derive(Eq, PartialEq)]
 enum Continue {
     Yes,
@@ -25,7 +113,9 @@ enum Continue {
 }
 
 pub mod msg {
-    use crate::state::{Action, Session, Subject};
+    use crate::state::{
+        editor::SessionEdit, Action, DateTime, RunningSession, Session, SpentTime, Subject, Topic,
+    };
     use druid::Selector;
 
     pub const STOP: Selector = Selector::new("zeitig.backend.stop");
@@ -33,17 +123,40 @@ pub mod msg {
     pub const ADD_ACTION: Selector<String> = Selector::new("zeitig.backend.add-action");
     pub const ADD_SUBJECT: Selector<String> = Selector::new("zeitig.backend.add-subject");
     pub const ADD_SESSION: Selector<Session> = Selector::new("zeitig.backend.add-session");
+    pub const SAVE_RUNNING: Selector<Option<RunningSession>> =
+        Selector::new("zeitig.backend.save-running");
+    /// An edit of the history, already applied to the `AppState`, with the new totals.
+    pub const EDIT_SESSIONS: Selector<(SessionEdit, Vec<(Topic, SpentTime)>)> =
+        Selector::new("zeitig.backend.edit-sessions");
+    pub const SET_PINNED: Selector<(Topic, bool)> = Selector::new("zeitig.backend.set-pinned");
 
     pub const ACTION_ADDED: Selector<Action> = Selector::new("zeitig.backend.action-added");
     pub const SUBJECT_ADDED: Selector<Subject> = Selector::new("zeitig.backend.subject-added");
 
+    /// Adding an action or subject failed, with the reason.
+    pub const CREATION_FAILED: Selector<String> = Selector::new("zeitig.backend.creation-failed");
+    /// Sends the command of the error with the given id again.
+    pub const RETRY: Selector<usize> = Selector::new("zeitig.backend.retry");
+    /// Forgets the error with the given id.
+    pub const DISMISS: Selector<usize> = Selector::new("zeitig.backend.dismiss");
+
+    /// A session has been written, identified by its start.
+    pub const SESSION_COMMITTED: Selector<DateTime> =
+        Selector::new("zeitig.backend.session-committed");
+
     pub const STOPPED: Selector = Selector::new("zeitig.backend.stopped");
-    pub const ERROR: Selector<String> = Selector::new("zeitig.backend.error");
 }
 
 #[derive(Default)]
 pub struct BackendController {
     sender: Option<Sender<BackendCommand>>,
+    /// The failed commands which can be retried, by the id of their error.
+    failed: HashMap<usize, BackendCommand>,
+    next_error: usize,
+    /// How many commands have been sent, to tell whether a reload is missing some of them.
+    sent: usize,
+    /// Whether a reload waits for the failed changes to the history to be retried or dismissed.
+    deferred_reload: bool,
 }
 
 impl BackendController {
@@ -58,21 +171,39 @@ impl BackendController {
         let sink = ctx.get_external_handle();
         let mut backend = Sqlite::new(paths::data_file()).unwrap();
         backend.setup().unwrap();
+        let mut version = backend.data_version().ok();
         thread::spawn(move || {
+            let mut handled = 0;
             loop {
-                let cmd = receiver.recv().expect(
-                    "The backend channel should not be closed while the backend is running.",
-                );
-                match Self::handle_command(cmd, &mut backend, &sink) {
+                let cmd = match receiver.recv_timeout(POLL_INTERVAL) {
+                    Ok(cmd) => cmd,
+                    Err(RecvTimeoutError::Timeout) => {
+                        let current = backend.data_version().ok();
+                        if current == version {
+                            continue;
+                        }
+                        version = current;
+                        log::info!("The database has been changed from outside, reloading.");
+                        BackendCommand::Reload
+                    }
+                    Err(RecvTimeoutError::Disconnected) => panic!(
+                        "The backend channel should not be closed while the backend is running."
+                    ),
+                };
+                if !cmd.is_reload() {
+                    handled += 1;
+                }
+                let command = cmd.clone();
+                match Self::handle_command(cmd, &mut backend, &sink, handled) {
                     Ok(Continue::Yes) => {}
                     Ok(Continue::No) => break,
                     Err(err) => {
-                        log::error!("{}", err);
-                        let message = err.to_string();
-                        if sink
-                            .submit_command(msg::ERROR, message, Target::Auto)
-                            .is_err()
-                        {
+                        log::error!("{}: {}", command.describe(), err);
+                        let failure = Failure {
+                            message: err.to_string(),
+                            command,
+                        };
+                        if sink.submit_command(FAILED, failure, Target::Auto).is_err() {
                             log::error!(
                                 "Backend event sink has been closed while the backend is still running."
                             );
@@ -91,10 +222,73 @@ impl BackendController {
         self.sender = Some(sender);
     }
 
+    fn report(&mut self, ctx: &mut EventCtx, data: &mut AppState, failure: &Failure) {
+        let command = failure.command.clone();
+        match command {
+            BackendCommand::AddAction(_) | BackendCommand::AddSubject(_) => {
+                ctx.submit_command(msg::CREATION_FAILED.with(failure.message.clone()));
+                return;
+            }
+            BackendCommand::Stop => return,
+            _ => {}
+        }
+        let message = format!("{}: {}", command.describe(), failure.message);
+        // Repeated failures of the periodic autosave replace each other, while every
+        // failed session or edit keeps its own error so that it can be retried.
+        let same_kind = if command.supersedes_earlier() {
+            let kind = mem::discriminant(&command);
+            self.failed
+                .iter()
+                .find(|(_, failed)| mem::discriminant(*failed) == kind)
+                .and_then(|(id, _)| data.backend_errors.iter().position(|e| e.id == *id))
+        } else {
+            None
+        };
+        match same_kind {
+            Some(index) => {
+                let id = data.backend_errors[index].id;
+                data.backend_errors[index].message = message;
+                self.failed.insert(id, command);
+            }
+            None => {
+                let id = self.next_error;
+                self.next_error += 1;
+                self.failed.insert(id, command);
+                data.backend_errors.push_back(BackendError { id, message });
+            }
+        }
+    }
+
+    /// Whether changes to the history failed to be written, which a reload would drop.
+    fn holds_unsaved(&self) -> bool {
+        self.failed.values().any(|command| {
+            matches!(
+                command,
+                BackendCommand::AddSession(..) | BackendCommand::EditSessions(..)
+            )
+        })
+    }
+
+    /// Sends the reload that was put off, once nothing unsaved is left.
+    fn resume_reload(&mut self) {
+        if self.deferred_reload && !self.holds_unsaved() {
+            self.deferred_reload = false;
+            self.send(BackendCommand::Reload);
+        }
+    }
+
+    fn send(&mut self, command: BackendCommand) {
+        if !command.is_reload() {
+            self.sent += 1;
+        }
+        self.sender.as_ref().unwrap().send(command).unwrap();
+    }
+
     fn handle_command(
         cmd: BackendCommand,
         backend: &mut dyn Backend,
         sink: &ExtEventSink,
+        handled: usize,
     ) -> Result<Continue, Box<dyn Error>> {
         match cmd {
             BackendCommand::AddAction(name) => {
@@ -105,9 +299,36 @@ impl BackendController {
                 let subject = backend.create_subject(&name)?;
                 sink.submit_command(msg::SUBJECT_ADDED, subject, Target::Auto)?;
             }
-            BackendCommand::AddSession(session, total_duration) => {
-                backend.add_session(&session)?;
-                backend.update_time(&session.topic, &total_duration)?;
+            BackendCommand::AddSession(session, total) => {
+                if let Err(err) = backend.commit_session(&session, &total) {
+                    if let Err(err) = outbox::push(&session) {
+                        log::error!("Could not keep the session in the outbox: {}", err);
+                    }
+                    return Err(err);
+                }
+                if let Err(err) = outbox::remove(&session) {
+                    log::warn!("Could not remove the session from the outbox: {}", err);
+                }
+                sink.submit_command(msg::SESSION_COMMITTED, session.started, Target::Auto)?;
+            }
+            BackendCommand::EditSessions(edit, totals) => {
+                backend.edit_sessions(&edit, &totals)?;
+            }
+            BackendCommand::SetPinned(topic, pinned) => {
+                backend.set_pinned(&topic, pinned)?;
+            }
+            BackendCommand::SaveRunning(running) => {
+                backend.save_running(running.as_ref())?;
+            }
+            BackendCommand::Reload => {
+                let content = backend.load_content()?;
+                let history = backend.load_history(&content)?;
+                let reloaded = Reloaded {
+                    content,
+                    history,
+                    handled,
+                };
+                sink.submit_command(RELOADED, reloaded, Target::Auto)?;
             }
             BackendCommand::Stop => return Ok(Continue::No),
         }
@@ -124,26 +345,74 @@ impl<W: Widget<AppState>> Controller<AppState, W> for BackendController {
         data: &mut AppState,
         env: &Env,
     ) {
-        let sender = self.sender.as_ref().unwrap();
         match event {
             Event::Command(cmd) if cmd.is(msg::ADD_ACTION) => {
                 let name = cmd.get_unchecked(msg::ADD_ACTION).to_owned();
-                sender.send(BackendCommand::AddAction(name)).unwrap();
+                self.send(BackendCommand::AddAction(name));
             }
             Event::Command(cmd) if cmd.is(msg::ADD_SUBJECT) => {
                 let name = cmd.get_unchecked(msg::ADD_SUBJECT).to_owned();
-                sender.send(BackendCommand::AddSubject(name)).unwrap();
+                self.send(BackendCommand::AddSubject(name));
             }
             Event::Command(cmd) if cmd.is(msg::ADD_SESSION) => {
+                // The session is already part of the in-memory total.
                 let session = cmd.get_unchecked(msg::ADD_SESSION).to_owned();
-                let past_duration = data.content.time_table.get(&session.topic);
-                let total_duration = past_duration + session.duration();
-                sender
-                    .send(BackendCommand::AddSession(session, total_duration))
-                    .unwrap();
+                let total = data.content.time_table.get(&session.topic);
+                data.pending_commits += 1;
+                self.send(BackendCommand::AddSession(session, total));
+            }
+            Event::Command(cmd) if cmd.is(msg::EDIT_SESSIONS) => {
+                let (edit, totals) = cmd.get_unchecked(msg::EDIT_SESSIONS).to_owned();
+                self.send(BackendCommand::EditSessions(edit, totals));
+            }
+            Event::Command(cmd) if cmd.is(msg::SET_PINNED) => {
+                let (topic, pinned) = cmd.get_unchecked(msg::SET_PINNED).to_owned();
+                self.send(BackendCommand::SetPinned(topic, pinned));
+            }
+            Event::Command(cmd) if cmd.is(msg::SAVE_RUNNING) => {
+                let running = cmd.get_unchecked(msg::SAVE_RUNNING).to_owned();
+                self.send(BackendCommand::SaveRunning(running));
+            }
+            Event::Command(cmd) if cmd.is(RELOADED) => {
+                let reloaded = cmd.get_unchecked(RELOADED);
+                if reloaded.handled != self.sent {
+                    // Changes made here since would be lost, load again once they are written.
+                    self.send(BackendCommand::Reload);
+                } else if self.holds_unsaved() {
+                    self.deferred_reload = true;
+                } else {
+                    data.reload(reloaded.content.clone(), reloaded.history.clone());
+                }
+            }
+            Event::Command(cmd) if cmd.is(msg::SESSION_COMMITTED) => {
+                data.pending_commits = data.pending_commits.saturating_sub(1);
+            }
+            Event::Command(cmd) if cmd.is(FAILED) => {
+                let failure = cmd.get_unchecked(FAILED);
+                if let BackendCommand::AddSession(..) = failure.command {
+                    data.pending_commits = data.pending_commits.saturating_sub(1);
+                }
+                self.report(ctx, data, failure);
+            }
+            Event::Command(cmd) if cmd.is(msg::RETRY) => {
+                let id = *cmd.get_unchecked(msg::RETRY);
+                data.backend_errors.retain(|e| e.id != id);
+                if let Some(command) = self.failed.remove(&id) {
+                    if let BackendCommand::AddSession(..) = command {
+                        data.pending_commits += 1;
+                    }
+                    self.send(command.refreshed(data));
+                }
+                self.resume_reload();
+            }
+            Event::Command(cmd) if cmd.is(msg::DISMISS) => {
+                let id = *cmd.get_unchecked(msg::DISMISS);
+                data.backend_errors.retain(|e| e.id != id);
+                self.failed.remove(&id);
+                self.resume_reload();
             }
             Event::Command(cmd) if cmd.is(msg::STOP) => {
-                sender.send(BackendCommand::Stop).unwrap();
+                self.send(BackendCommand::Stop);
             }
             _ => child.event(ctx, event, data, env),
         }
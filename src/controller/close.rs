@@ -30,6 +30,7 @@ impl<W: Widget<AppState>> Controller<AppState, W> for CloseController {
                     if data.active.is_some() {
                         tracker::end_session(ctx, data);
                     }
+                    ctx.submit_command(backend_msg::SAVE_RUNNING.with(None));
                     ctx.submit_command(backend_msg::STOP);
                 }
             }
@@ -0,0 +1,29 @@
+use druid::{keyboard_types::Key, widget::Controller, Env, Event, EventCtx, Widget};
+use std::any::Any;
+
+/// Offers key presses to a callback before the child sees them.
+///
+/// If the callback returns `true`, the key is considered handled.
+pub struct KeyController<D> {
+    callback: Box<dyn Fn(&mut EventCtx, &mut D, &Key) -> bool>,
+}
+
+impl<D> KeyController<D> {
+    pub fn new(callback: impl Fn(&mut EventCtx, &mut D, &Key) -> bool + Any) -> Self {
+        Self {
+            callback: Box::new(callback),
+        }
+    }
+}
+
+impl<D, W: Widget<D>> Controller<D, W> for KeyController<D> {
+    fn event(&mut self, child: &mut W, ctx: &mut EventCtx, event: &Event, data: &mut D, env: &Env) {
+        if let Event::KeyDown(event) = event {
+            if (self.callback)(ctx, data, &event.key) {
+                ctx.set_handled();
+                return;
+            }
+        }
+        child.event(ctx, event, data, env);
+    }
+}
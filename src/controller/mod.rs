@@ -10,6 +10,12 @@ pub use ticker::Ticker;
 mod enter;
 pub use enter::EnterController;
 
+mod keys;
+pub use keys::KeyController;
+
+mod shortcuts;
+pub use shortcuts::Shortcuts;
+
 mod backend;
 
 pub use backend::msg as backend_msg;
@@ -17,3 +23,9 @@ pub use backend::BackendController;
 
 mod close;
 pub use close::CloseController;
+
+mod settings_saver;
+pub use settings_saver::SettingsSaver;
+
+mod remote;
+pub use remote::RemoteController;
@@ -0,0 +1,127 @@
+use crate::{
+    controller::backend_msg,
+    remote::{self, Request},
+    state::{manual::ManualEntry, AppState, Settings, Topic},
+    ui::tracker,
+};
+use druid::{widget::Controller, Env, Event, EventCtx, LifeCycle, LifeCycleCtx, Widget};
+use serde_json::Value;
+
+/// Serves requests from outside of the application.
+pub struct RemoteController;
+
+impl RemoteController {
+    fn handle(ctx: &mut EventCtx, data: &mut AppState, request: &Request) -> Result<Value, String> {
+        match request {
+            Request::Show => {
+                ctx.window().bring_to_front_and_focus();
+                return Ok(Value::Null);
+            }
+            Request::Status => {}
+            Request::Content => return Ok(remote::content(data)),
+            Request::TimeTable => return Ok(remote::time_table(data)),
+            Request::History { from, to } => {
+                return remote::history(data, from.as_deref(), to.as_deref())
+            }
+            Request::Start { action, subject } => {
+                if data.active.is_some() {
+                    return Err("A session is already running, use switch to change it.".into());
+                }
+                let topic = Self::find_topic(data, action, subject)?;
+                tracker::select_topic(ctx, data, topic);
+                data.start_session();
+            }
+            Request::Stop => {
+                if data.active.is_none() {
+                    return Err("No session is running.".into());
+                }
+                tracker::end_session(ctx, data);
+            }
+            Request::Switch { action, subject } => {
+                let topic = Self::find_topic(data, action, subject)?;
+                tracker::end_session(ctx, data);
+                tracker::select_topic(ctx, data, topic);
+                data.start_session();
+            }
+            Request::Add {
+                action,
+                subject,
+                started,
+                ended,
+            } => {
+                let entry = ManualEntry {
+                    action: action.clone(),
+                    subject: subject.clone(),
+                    started: started.clone(),
+                    ended: ended.clone(),
+                    error: String::new(),
+                };
+                let session = entry.session(data)?;
+                data.record(session.clone());
+                ctx.submit_command(backend_msg::ADD_SESSION.with(session));
+            }
+        }
+        Ok(remote::status(data))
+    }
+
+    fn listen(ctx: &mut LifeCycleCtx, settings: &Settings) {
+        #[cfg(unix)]
+        {
+            if let Err(err) = remote::socket::spawn(ctx.get_external_handle()) {
+                log::error!("Could not open the control socket: {}", err);
+            }
+        }
+        if settings.http_enabled {
+            let sink = ctx.get_external_handle();
+            let token = settings.http_token.clone();
+            if let Err(err) = remote::http::spawn(sink, &settings.http_address, token) {
+                log::error!("Could not start the HTTP server: {}", err);
+            }
+        }
+    }
+
+    fn find_topic(data: &AppState, action: &str, subject: &str) -> Result<Topic, String> {
+        let action = data
+            .content
+            .find_action_by_name(action)
+            .ok_or_else(|| format!("There is no action named '{}'.", action))?;
+        let subject = data
+            .content
+            .find_subject_by_name(subject)
+            .ok_or_else(|| format!("There is no subject named '{}'.", subject))?;
+        Ok(Topic { action, subject })
+    }
+}
+
+impl<W: Widget<AppState>> Controller<AppState, W> for RemoteController {
+    fn event(
+        &mut self,
+        child: &mut W,
+        ctx: &mut EventCtx,
+        event: &Event,
+        data: &mut AppState,
+        env: &Env,
+    ) {
+        match event {
+            Event::Command(cmd) if cmd.is(remote::REQUEST) => {
+                let call = cmd.get_unchecked(remote::REQUEST);
+                call.reply(Self::handle(ctx, data, &call.request));
+            }
+            _ => child.event(ctx, event, data, env),
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        child: &mut W,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &AppState,
+        env: &Env,
+    ) {
+        if let LifeCycle::WidgetAdded = event {
+            Self::listen(ctx, &data.settings);
+        }
+        child.lifecycle(ctx, event, data, env)
+    }
+}
@@ -0,0 +1,24 @@
+use druid::{widget::Controller, Data, Env, UpdateCtx, Widget};
+
+use crate::state::AppState;
+
+/// Persists the settings whenever they are changed.
+pub struct SettingsSaver;
+
+impl<W: Widget<AppState>> Controller<AppState, W> for SettingsSaver {
+    fn update(
+        &mut self,
+        child: &mut W,
+        ctx: &mut UpdateCtx,
+        old_data: &AppState,
+        data: &AppState,
+        env: &Env,
+    ) {
+        if !old_data.settings.same(&data.settings) {
+            if let Err(err) = data.settings.save() {
+                log::error!("Could not save the settings: {}", err);
+            }
+        }
+        child.update(ctx, old_data, data, env)
+    }
+}
@@ -0,0 +1,32 @@
+use druid::{widget::Controller, Command, Env, Event, EventCtx, HotKey, Widget};
+
+/// Submits a command whenever one of its key combinations is pressed
+/// anywhere inside the child.
+#[derive(Default)]
+pub struct Shortcuts {
+    bindings: Vec<(HotKey, Command)>,
+}
+
+impl Shortcuts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn bind(mut self, hotkey: HotKey, command: impl Into<Command>) -> Self {
+        self.bindings.push((hotkey, command.into()));
+        self
+    }
+}
+
+impl<D, W: Widget<D>> Controller<D, W> for Shortcuts {
+    fn event(&mut self, child: &mut W, ctx: &mut EventCtx, event: &Event, data: &mut D, env: &Env) {
+        if let Event::KeyDown(key) = event {
+            if let Some((_, command)) = self.bindings.iter().find(|(hk, _)| hk.matches(key)) {
+                ctx.submit_command(command.clone());
+                ctx.set_handled();
+                return;
+            }
+        }
+        child.event(ctx, event, data, env);
+    }
+}
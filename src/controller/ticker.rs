@@ -1,10 +1,7 @@
 use druid::{widget::Controller, Env, Event, EventCtx, TimerToken, Widget};
-use std::time::Duration;
 
 use crate::{controller::SAVE_NOW, state::AppState};
 
-const INTERVAL: Duration = Duration::from_secs(1);
-
 pub struct Ticker {
     timer: Option<TimerToken>,
 }
@@ -26,9 +23,9 @@ impl<W: Widget<AppState>> Controller<AppState, W> for Ticker {
     ) {
         if let Event::Timer(token) = event {
             if Some(*token) == self.timer {
-                if let Some(session) = data.active.as_mut() {
-                    *session.duration += INTERVAL;
-                    self.timer = Some(ctx.request_timer(INTERVAL));
+                if data.is_ticking() {
+                    data.tick();
+                    self.timer = Some(ctx.request_timer(data.settings.tick_interval()));
                 }
             }
         }
@@ -44,13 +41,23 @@ impl<W: Widget<AppState>> Controller<AppState, W> for Ticker {
         env: &Env,
     ) {
         match (old_data.active.is_some(), data.active.is_some()) {
-            (false, true) => self.timer = Some(ctx.request_timer(INTERVAL)),
+            (false, true) => self.timer = Some(ctx.request_timer(data.settings.tick_interval())),
             (true, false) => {
                 self.timer = None;
                 ctx.submit_command(SAVE_NOW)
             }
             _ => (),
         }
+        match (old_data.is_ticking(), data.is_ticking()) {
+            (false, true) if self.timer.is_none() => {
+                self.timer = Some(ctx.request_timer(data.settings.tick_interval()))
+            }
+            (true, false) if data.active.is_some() => {
+                self.timer = None;
+                ctx.submit_command(SAVE_NOW)
+            }
+            _ => (),
+        }
         child.update(ctx, old_data, data, env);
     }
 }
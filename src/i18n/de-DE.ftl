@@ -0,0 +1,219 @@
+## Formats
+
+# The time spent on something, as shown everywhere.
+duration = { $hours } Std. { $minutes } Min. { $seconds } Sek.
+duration-decimal = { $hours } Std.
+# Whole days split off a long duration, followed by the rest of it.
+duration-days = { $days } T. { $rest }
+decimal-separator = ,
+# Patterns for dates, see the format descriptions of the time crate.
+date-format = %d.%m.%Y
+short-date-format = %d.%m.
+
+weekday-1 = Montag
+weekday-2 = Dienstag
+weekday-3 = Mittwoch
+weekday-4 = Donnerstag
+weekday-5 = Freitag
+weekday-6 = Samstag
+weekday-7 = Sonntag
+
+weekday-short-1 = Mo
+weekday-short-2 = Di
+weekday-short-3 = Mi
+weekday-short-4 = Do
+weekday-short-5 = Fr
+weekday-short-6 = Sa
+weekday-short-7 = So
+
+month-1 = Jan.
+month-2 = Feb.
+month-3 = März
+month-4 = Apr.
+month-5 = Mai
+month-6 = Juni
+month-7 = Juli
+month-8 = Aug.
+month-9 = Sep.
+month-10 = Okt.
+month-11 = Nov.
+month-12 = Dez.
+
+## Windows
+
+window-settings = Einstellungen
+window-insights = Auswertung
+window-table = Zeittabelle
+window-add-session = Sitzung nachtragen
+window-history = Verlauf
+window-calendar = Kalender
+
+## Tracker
+
+no-action = Keine Tätigkeit
+no-subject = Kein Thema
+session-running = Sitzung: { $duration }
+session-paused = Sitzung: { $duration } (pausiert)
+session-stopped = Sitzung: läuft nicht
+total = Gesamt: { $duration }
+total-saving = Gesamt: { $duration } (wird gespeichert)
+start = Start
+stop = Stopp
+pause = Pause
+resume = Weiter
+filter = Filtern
+new-item = Neuer Eintrag
+cancel = Abbrechen
+add = Hinzufügen
+add-session = Nachtragen
+settings = Einstellungen
+insights = Auswertung
+calendar = Kalender
+history = Verlauf
+table = Tabelle
+retry = Wiederholen
+dismiss = Verwerfen
+resolve = Auflösen
+overlaps =
+    { $count ->
+        [one] Zwei Sitzungen überschneiden sich.
+       *[other] { $count } Paare von Sitzungen überschneiden sich.
+    }
+
+## Creation dialog
+
+what-to-add = Was soll hinzugefügt werden?
+action = Tätigkeit
+subject = Thema
+add-action = Neue Tätigkeit:
+add-subject = Neues Thema:
+action-needs-name = Die Tätigkeit braucht einen Namen.
+subject-needs-name = Das Thema braucht einen Namen.
+action-exists = Die Tätigkeit '{ $name }' gibt es schon.
+subject-exists = Das Thema '{ $name }' gibt es schon.
+
+## Command palette
+
+command-toggle-session = Sitzung starten / stoppen
+command-toggle-pause = Sitzung pausieren / fortsetzen
+command-new-item = Neuer Eintrag
+command-add-session = Sitzung nachtragen
+command-switch-topic = Thema wechseln
+command-toggle-pinned = Aktuelles Thema anheften / lösen
+command-palette = Befehlspalette
+command-insights = Auswertung öffnen
+command-calendar = Kalender öffnen
+command-history = Verlauf öffnen
+command-table = Zeittabelle öffnen
+command-settings = Einstellungen öffnen
+# Typing it narrows the palette down to the topics, so it should be distinct.
+command-switch-to = Wechseln zu
+
+## History
+
+today = Heute
+find-overlaps = Überschneidungen finden
+merge-with-next = Mit nächster zusammenführen
+trim = Kürzen
+split = Teilen
+discard = Verwerfen
+sessions-overlap = Diese Sitzungen überschneiden sich, ändere eine davon:
+split-session = { $session } teilen
+split-at = um
+split-placeholder = HH:MM
+continuing-with = weiter mit
+click-to-split = Klicke auf eine Sitzung, um sie zu teilen.
+no-history = Es wird kein Verlauf angezeigt.
+no-selection = Es ist keine Sitzung ausgewählt.
+no-conflict = Es wird keine Überschneidung angezeigt.
+no-overlaps = Keine Sitzungen überschneiden sich.
+no-later-session = An diesem Tag gibt es keine spätere Sitzung.
+invalid-time = Ungültige Uhrzeit '{ $input }'.
+unknown-action = Es gibt keine Tätigkeit namens '{ $name }'.
+unknown-subject = Es gibt kein Thema namens '{ $name }'.
+split-outside = Die Sitzung muss innerhalb geteilt werden.
+merge-other-topic = Nur Sitzungen desselben Themas können zusammengeführt werden.
+merge-overlapping = Die Sitzungen überschneiden sich, löse das zuerst auf.
+merge-in-between = Eine andere Sitzung liegt dazwischen.
+trim-within = Die Sitzung liegt innerhalb der anderen, verwirf sie stattdessen.
+trim-enclosing = Die Sitzung umschließt die andere, teile sie stattdessen.
+split-not-enclosing = Nur eine Sitzung, die die andere umschließt, kann geteilt werden.
+
+## Calendar
+
+week-of = Woche vom { $date }
+color-by-action = Nach Tätigkeit färben
+color-by-subject = Nach Thema färben
+
+## Insights
+
+activity = Aktivität
+filter-all = Alles
+filter-action = Gewählte Tätigkeit
+filter-subject = Gewähltes Thema
+time-per = Zeit pro
+period-day = Tag
+period-week = Woche
+period-month = Monat
+stack-by = nach
+week-summary = Woche vom { $date } ({ $duration })
+
+## Time table
+
+range-all = Gesamte Zeit
+range-week = Diese Woche
+range-month = Dieser Monat
+range-year = Dieses Jahr
+range-custom = Von - bis
+# The days are always entered as YYYY-MM-DD.
+range-from = Von (JJJJ-MM-TT)
+range-to = Bis (JJJJ-MM-TT)
+invalid-day = Ungültiger Tag '{ $input }', erwartet wird JJJJ-MM-TT.
+sort-by = Sortieren nach
+sort-total = Gesamt
+sort-name = Name
+table-total = Gesamt
+
+## Adding a session
+
+started-at = Beginn
+started-placeholder = HH:MM oder JJJJ-MM-TT HH:MM
+ended-at = Ende oder Dauer
+ended-placeholder = HH:MM oder 1h30m
+invalid-start = Ungültiger Beginn '{ $input }'.
+invalid-end = Ungültiges Ende oder ungültige Dauer '{ $input }'.
+end-before-start = Die Sitzung muss nach ihrem Beginn enden.
+end-in-future = Die Sitzung muss in der Vergangenheit enden.
+overlaps-session = Die Sitzung überschneidet sich mit { $action } { $subject } von { $from } bis { $to }.
+overlaps-running = Die Sitzung überschneidet sich mit der laufenden.
+
+## Saving errors
+
+error-add-action = Die Tätigkeit konnte nicht hinzugefügt werden
+error-add-subject = Das Thema konnte nicht hinzugefügt werden
+error-add-session = Eine Sitzung konnte nicht gespeichert werden
+error-edit-sessions = Die geänderten Sitzungen konnten nicht gespeichert werden
+error-set-pinned = Die angehefteten Themen konnten nicht gespeichert werden
+error-save-running = Die laufende Sitzung konnte nicht gespeichert werden
+error-reload = Die Daten konnten nicht neu geladen werden
+error-stop = Das Backend konnte nicht beendet werden
+
+## Settings
+
+unit-seconds = { $value } s
+unit-pixels = { $value } px
+discard-threshold = Sitzungen verwerfen bis
+autosave-interval = Speichern alle
+tick-interval = Zeit aktualisieren alle
+window-width = Fensterbreite
+window-height = Fensterhöhe
+http-enabled = HTTP-API anbieten
+http-address = Adresse
+http-token = Token
+duration-format = Dauer anzeigen als
+setting-duration-days = Ganze Tage abtrennen
+theme = Farbschema
+accent-color = Akzentfarbe
+language = Sprache
+language-system = Systemsprache
+applied-on-restart = Fenstergröße, HTTP-API und Sprache werden beim nächsten Start übernommen.
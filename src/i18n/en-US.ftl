@@ -0,0 +1,219 @@
+## Formats
+
+# The time spent on something, as shown everywhere.
+duration = { $hours }h { $minutes }m { $seconds }s
+duration-decimal = { $hours }h
+# Whole days split off a long duration, followed by the rest of it.
+duration-days = { $days }d { $rest }
+decimal-separator = .
+# Patterns for dates, see the format descriptions of the time crate.
+date-format = %m/%d/%Y
+short-date-format = %m/%d
+
+weekday-1 = Monday
+weekday-2 = Tuesday
+weekday-3 = Wednesday
+weekday-4 = Thursday
+weekday-5 = Friday
+weekday-6 = Saturday
+weekday-7 = Sunday
+
+weekday-short-1 = Mon
+weekday-short-2 = Tue
+weekday-short-3 = Wed
+weekday-short-4 = Thu
+weekday-short-5 = Fri
+weekday-short-6 = Sat
+weekday-short-7 = Sun
+
+month-1 = Jan
+month-2 = Feb
+month-3 = Mar
+month-4 = Apr
+month-5 = May
+month-6 = Jun
+month-7 = Jul
+month-8 = Aug
+month-9 = Sep
+month-10 = Oct
+month-11 = Nov
+month-12 = Dec
+
+## Windows
+
+window-settings = Settings
+window-insights = Insights
+window-table = Time Table
+window-add-session = Add Session
+window-history = History
+window-calendar = Calendar
+
+## Tracker
+
+no-action = No Action
+no-subject = No Subject
+session-running = Session: { $duration }
+session-paused = Session: { $duration } (paused)
+session-stopped = Session: not running
+total = Total: { $duration }
+total-saving = Total: { $duration } (saving)
+start = Start
+stop = Stop
+pause = Pause
+resume = Resume
+filter = Filter
+new-item = New Item
+cancel = Cancel
+add = Add
+add-session = Add Session
+settings = Settings
+insights = Insights
+calendar = Calendar
+history = History
+table = Table
+retry = Retry
+dismiss = Dismiss
+resolve = Resolve
+overlaps =
+    { $count ->
+        [one] Two sessions overlap.
+       *[other] { $count } pairs of sessions overlap.
+    }
+
+## Creation dialog
+
+what-to-add = What to add?
+action = Action
+subject = Subject
+add-action = Add new action:
+add-subject = Add new subject:
+action-needs-name = The action needs a name.
+subject-needs-name = The subject needs a name.
+action-exists = The action '{ $name }' already exists.
+subject-exists = The subject '{ $name }' already exists.
+
+## Command palette
+
+command-toggle-session = Start / Stop Session
+command-toggle-pause = Pause / Resume Session
+command-new-item = New Item
+command-add-session = Add Past Session
+command-switch-topic = Switch Topic
+command-toggle-pinned = Pin / Unpin Current Topic
+command-palette = Command Palette
+command-insights = Open Insights
+command-calendar = Open Calendar
+command-history = Open History
+command-table = Open Time Table
+command-settings = Open Settings
+# Typing it narrows the palette down to the topics, so it should be distinct.
+command-switch-to = Switch to
+
+## History
+
+today = Today
+find-overlaps = Find overlaps
+merge-with-next = Merge with next
+trim = Trim
+split = Split
+discard = Discard
+sessions-overlap = These sessions overlap, change one of them:
+split-session = Split { $session }
+split-at = at
+split-placeholder = HH:MM
+continuing-with = continuing with
+click-to-split = Click a session to split it.
+no-history = No history is shown.
+no-selection = No session is selected.
+no-conflict = No conflict is shown.
+no-overlaps = There are no overlapping sessions.
+no-later-session = There is no later session on this day.
+invalid-time = Invalid time '{ $input }'.
+unknown-action = There is no action named '{ $name }'.
+unknown-subject = There is no subject named '{ $name }'.
+split-outside = The split has to lie within the session.
+merge-other-topic = Only sessions of the same topic can be merged.
+merge-overlapping = The sessions overlap, resolve that first.
+merge-in-between = Another session lies between them.
+trim-within = The session lies within the other one, discard it instead.
+trim-enclosing = The session encloses the other one, split it instead.
+split-not-enclosing = Only a session enclosing the other one can be split.
+
+## Calendar
+
+week-of = Week of { $date }
+color-by-action = Color by action
+color-by-subject = Color by subject
+
+## Insights
+
+activity = Activity
+filter-all = Everything
+filter-action = Selected action
+filter-subject = Selected subject
+time-per = Time per
+period-day = Day
+period-week = Week
+period-month = Month
+stack-by = by
+week-summary = Week of { $date } ({ $duration })
+
+## Time table
+
+range-all = All time
+range-week = This week
+range-month = This month
+range-year = This year
+range-custom = From - to
+# The days are always entered as YYYY-MM-DD.
+range-from = From (YYYY-MM-DD)
+range-to = To (YYYY-MM-DD)
+invalid-day = Invalid day '{ $input }', expected YYYY-MM-DD.
+sort-by = Sort by
+sort-total = Total
+sort-name = Name
+table-total = Total
+
+## Adding a session
+
+started-at = Start
+started-placeholder = HH:MM or YYYY-MM-DD HH:MM
+ended-at = End or duration
+ended-placeholder = HH:MM or 1h30m
+invalid-start = Invalid start '{ $input }'.
+invalid-end = Invalid end or duration '{ $input }'.
+end-before-start = The session has to end after it started.
+end-in-future = The session has to end in the past.
+overlaps-session = The session overlaps { $action } { $subject } from { $from } to { $to }.
+overlaps-running = The session overlaps the running one.
+
+## Saving errors
+
+error-add-action = Could not add the action
+error-add-subject = Could not add the subject
+error-add-session = Could not save a session
+error-edit-sessions = Could not save the changed sessions
+error-set-pinned = Could not save the pinned topics
+error-save-running = Could not save the running session
+error-reload = Could not reload the data
+error-stop = Could not stop the backend
+
+## Settings
+
+unit-seconds = { $value }s
+unit-pixels = { $value }px
+discard-threshold = Discard sessions up to
+autosave-interval = Save every
+tick-interval = Update the timer every
+window-width = Window width
+window-height = Window height
+http-enabled = Serve the HTTP API
+http-address = Address
+http-token = Token
+duration-format = Show durations as
+setting-duration-days = Split off whole days
+theme = Theme
+accent-color = Accent color
+language = Language
+language-system = System
+applied-on-restart = The window size, the HTTP API and the language are applied on the next start.
@@ -0,0 +1,299 @@
+//! Translations of the user interface, kept as Fluent resources next to this module.
+use crate::state::{DurationFormat, Settings, SpentTime};
+use druid::{Env, Key};
+use fluent_bundle::{concurrent::FluentBundle, FluentArgs, FluentResource, FluentValue};
+use once_cell::sync::OnceCell;
+use unic_langid::LanguageIdentifier;
+
+/// The bundled languages, the first one being used for anything the others lack.
+static RESOURCES: &[(&str, &str)] = &[
+    ("en-US", include_str!("en-US.ftl")),
+    ("de-DE", include_str!("de-DE.ftl")),
+];
+
+/// The languages to choose from in the settings, each named in itself.
+pub static LANGUAGES: &[(&str, &str)] = &[("English", "en-US"), ("Deutsch", "de-DE")];
+
+static LOCALIZER: OnceCell<Localizer> = OnceCell::new();
+
+/// The duration format chosen in the settings, as its position in `DURATION_FORMATS`.
+pub const DURATION_FORMAT: Key<u64> = Key::new("zeitig.i18n.duration-format");
+/// Whether whole days are split off long durations.
+pub const DURATION_DAYS: Key<bool> = Key::new("zeitig.i18n.duration-days");
+
+const DURATION_FORMATS: [DurationFormat; 3] = [
+    DurationFormat::Units,
+    DurationFormat::Clock,
+    DurationFormat::Decimal,
+];
+
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+struct Localizer {
+    /// The chosen language followed by the fallback.
+    bundles: Vec<FluentBundle<FluentResource>>,
+}
+
+/// Selects the language, given like `de-DE`, or the one of the system if it is empty.
+///
+/// Has to be called before anything is translated, as the language can not change later on.
+pub fn init(language: &str) {
+    let language = if language.is_empty() {
+        system_language()
+    } else {
+        language.to_string()
+    };
+    if LOCALIZER.set(Localizer::new(&language)).is_err() {
+        log::warn!("The language has already been chosen.");
+    }
+}
+
+fn localizer() -> &'static Localizer {
+    LOCALIZER.get_or_init(|| Localizer::new(&system_language()))
+}
+
+#[cfg(not(test))]
+fn system_language() -> String {
+    druid::Application::get_locale()
+}
+
+/// The tests check the English messages, whatever the system is set to.
+#[cfg(test)]
+fn system_language() -> String {
+    "en-US".to_string()
+}
+
+/// The message with the given id in the chosen language.
+pub fn tr(id: &str) -> String {
+    localizer().format(id, None)
+}
+
+/// The message with the given id in the chosen language, filled in with `args`.
+pub fn tr_args<'a>(id: &str, args: &[(&'a str, FluentValue<'a>)]) -> String {
+    let args: FluentArgs = args.iter().cloned().collect();
+    localizer().format(id, Some(&args))
+}
+
+/// The message with the given id, with its `$duration` filled in.
+pub fn tr_duration(id: &str, time: &SpentTime, env: &Env) -> String {
+    tr_args(id, &[("duration", duration(time, env).into())])
+}
+
+/// Puts the duration format of `settings` into `env`, so that every widget
+/// is updated when it changes.
+pub fn set_duration_format(env: &mut Env, settings: &Settings) {
+    let format = DURATION_FORMATS
+        .iter()
+        .position(|format| *format == settings.duration_format)
+        .unwrap_or(0);
+    env.set(DURATION_FORMAT, format as u64);
+    env.set(DURATION_DAYS, settings.duration_days);
+}
+
+/// `time` in the duration format of `env`.
+pub fn duration(time: &SpentTime, env: &Env) -> String {
+    let format = env
+        .try_get(DURATION_FORMAT)
+        .ok()
+        .and_then(|format| DURATION_FORMATS.get(format as usize).copied())
+        .unwrap_or(DurationFormat::Units);
+    let with_days = env.try_get(DURATION_DAYS).unwrap_or(false);
+    format_duration(time, format, with_days)
+}
+
+/// `time` in the given format, with whole days split off if `with_days` is set.
+pub fn format_duration(time: &SpentTime, format: DurationFormat, with_days: bool) -> String {
+    let mut total = time.as_secs();
+    let days = if with_days { total / SECS_PER_DAY } else { 0 };
+    total -= days * SECS_PER_DAY;
+    let rest = match format {
+        DurationFormat::Units => tr_args(
+            "duration",
+            &[
+                ("hours", (total / 60 / 60).into()),
+                ("minutes", ((total / 60) % 60).into()),
+                ("seconds", (total % 60).into()),
+            ],
+        ),
+        DurationFormat::Clock => format!("{}:{:02}", total / 60 / 60, (total / 60) % 60),
+        DurationFormat::Decimal => {
+            let hours = decimal(total as f64 / 3600.0, 2);
+            tr_args("duration-decimal", &[("hours", hours.into())])
+        }
+    };
+    if days > 0 {
+        tr_args(
+            "duration-days",
+            &[("days", days.into()), ("rest", rest.into())],
+        )
+    } else {
+        rest
+    }
+}
+
+/// `value` with the given number of decimal places and the decimal separator of the language.
+pub fn decimal(value: f64, places: usize) -> String {
+    format!("{:.*}", places, value).replace('.', &tr("decimal-separator"))
+}
+
+pub fn date(date: &time::Date) -> String {
+    date.format(&tr("date-format"))
+}
+
+/// Day and month, without the year.
+pub fn short_date(date: &time::Date) -> String {
+    date.format(&tr("short-date-format"))
+}
+
+pub fn weekday(date: &time::Date) -> String {
+    tr(&format!("weekday-{}", date.weekday().number_from_monday()))
+}
+
+pub fn short_weekday(date: &time::Date) -> String {
+    tr(&format!(
+        "weekday-short-{}",
+        date.weekday().number_from_monday()
+    ))
+}
+
+/// The abbreviated name of the month.
+pub fn month(date: &time::Date) -> String {
+    tr(&format!("month-{}", date.month()))
+}
+
+impl Localizer {
+    fn new(language: &str) -> Localizer {
+        // The system may give something like `de_DE.UTF-8`.
+        let language = language
+            .split('.')
+            .next()
+            .unwrap_or_default()
+            .replace('_', "-");
+        let requested: Option<LanguageIdentifier> = language.parse().ok();
+        let chosen = RESOURCES
+            .iter()
+            .position(|(id, _)| {
+                let id: LanguageIdentifier = id.parse().unwrap();
+                requested
+                    .as_ref()
+                    .map_or(false, |r| r.language == id.language)
+            })
+            .unwrap_or(0);
+        let mut bundles = vec![bundle(RESOURCES[chosen])];
+        if chosen != 0 {
+            bundles.push(bundle(RESOURCES[0]));
+        }
+        Localizer { bundles }
+    }
+
+    fn format(&self, id: &str, args: Option<&FluentArgs>) -> String {
+        for bundle in &self.bundles {
+            if let Some(pattern) = bundle.get_message(id).and_then(|message| message.value) {
+                let mut errors = Vec::new();
+                let text = bundle.format_pattern(pattern, args, &mut errors);
+                if !errors.is_empty() {
+                    log::warn!("Could not translate '{}': {:?}", id, errors);
+                }
+                return text.into_owned();
+            }
+        }
+        log::warn!("There is no translation for '{}'.", id);
+        id.to_string()
+    }
+}
+
+fn bundle((id, source): (&str, &str)) -> FluentBundle<FluentResource> {
+    let language: LanguageIdentifier = id.parse().unwrap();
+    let resource = FluentResource::try_new(source.to_string())
+        .unwrap_or_else(|_| panic!("The resource for {} is invalid.", id));
+    let mut bundle = FluentBundle::new(&[language]);
+    // The directional isolation marks show up as boxes in some fonts.
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(resource)
+        .unwrap_or_else(|_| panic!("The resource for {} has duplicate messages.", id));
+    bundle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bundle, format_duration, set_duration_format, tr_args, LANGUAGES, RESOURCES};
+    use crate::state::{DurationFormat, Settings, SpentTime};
+    use druid::Env;
+    use std::time::Duration;
+
+    fn secs(secs: u64) -> SpentTime {
+        SpentTime::from(Duration::from_secs(secs))
+    }
+
+    #[test]
+    fn every_language_has_a_valid_resource() {
+        for resource in RESOURCES {
+            bundle(*resource);
+        }
+        for (_, id) in LANGUAGES {
+            assert!(RESOURCES.iter().any(|(resource, _)| resource == id));
+        }
+    }
+
+    #[test]
+    fn days_use_the_duration_message() {
+        let text = tr_args(
+            "duration-days",
+            &[("days", 2.into()), ("rest", "1:05".into())],
+        );
+        assert_eq!(text, "2d 1:05");
+    }
+
+    #[test]
+    fn durations_in_each_format() {
+        let time = secs(7 * 3600 + 15 * 60 + 30);
+        assert_eq!(
+            format_duration(&time, DurationFormat::Units, false),
+            "7h 15m 30s"
+        );
+        assert_eq!(format_duration(&time, DurationFormat::Clock, false), "7:15");
+        assert_eq!(
+            format_duration(&time, DurationFormat::Decimal, false),
+            "7.26h"
+        );
+        assert_eq!(
+            format_duration(&secs(0), DurationFormat::Clock, false),
+            "0:00"
+        );
+    }
+
+    #[test]
+    fn durations_with_days() {
+        let time = secs(2 * 24 * 3600 + 3600 + 5 * 60);
+        assert_eq!(
+            format_duration(&time, DurationFormat::Units, true),
+            "2d 1h 5m 0s"
+        );
+        assert_eq!(
+            format_duration(&time, DurationFormat::Clock, true),
+            "2d 1:05"
+        );
+        assert_eq!(
+            format_duration(&time, DurationFormat::Clock, false),
+            "49:05"
+        );
+        assert_eq!(
+            format_duration(&secs(3600), DurationFormat::Clock, true),
+            "1:00"
+        );
+    }
+
+    #[test]
+    fn duration_follows_the_env() {
+        let mut env = Env::default();
+        let time = secs(90 * 60);
+        assert_eq!(super::duration(&time, &env), "1h 30m 0s");
+        let settings = Settings {
+            duration_format: DurationFormat::Decimal,
+            ..Settings::default()
+        };
+        set_duration_format(&mut env, &settings);
+        assert_eq!(super::duration(&time, &env), "1.50h");
+    }
+}
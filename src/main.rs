@@ -1,30 +1,74 @@
 use druid::{AppLauncher, WindowDesc};
 use std::error::Error;
 
+mod cli;
 mod controller;
+mod i18n;
+mod remote;
 mod state;
 mod ui;
 mod widgets;
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let window = WindowDesc::new(ui::tracker)
+    let settings = state::Settings::load();
+    i18n::init(&settings.language);
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        cli::run(&args);
+        return Ok(());
+    }
+    // Held until the tracker closes, so that commands are handed to it.
+    let _lock = match cli::lock_or_show() {
+        Some(lock) => lock,
+        None => return Ok(()),
+    };
+
+    let window = WindowDesc::new(|| ui::themed(ui::tracker()))
         .title("Zeitig")
-        .window_size((300.0, 400.0));
+        .window_size(settings.window_size());
 
     use state::backend::Backend;
     let mut backend = state::backend::Sqlite::new(state::paths::data_file())?;
     backend.setup()?;
-    let content = backend.load_content()?;
-    let history = backend.load_history(&content)?;
+    let mut content = backend.load_content()?;
+    let mut history = backend.load_history(&content)?;
+    if let Err(err) = state::outbox::replay(&mut backend, &mut content, &mut history) {
+        log::error!("Could not replay the outbox: {}", err);
+    }
+    let running = backend.load_running(&content)?;
     backend.close()?;
-    let state = state::AppState {
+    let mut state = state::AppState {
         content,
         history,
         setup: state::Setup::default(),
         active: None,
+        clock: state::SharedClock::default(),
+        settings,
+        insights: None,
+        calendar: None,
+        week: None,
+        heatmap_filter: state::aggregate::FilterBy::All,
+        heatmap: None,
+        chart: state::aggregate::ChartSetup::default(),
+        chart_view: None,
+        matrix: state::matrix::MatrixSetup::default(),
+        matrix_view: None,
+        manual: state::manual::ManualEntry::default(),
+        editor: None,
+        quick: Default::default(),
+        overlaps: 0,
+        backend_errors: Default::default(),
+        pending_commits: 0,
     };
+    state.check_overlaps();
+    state.refresh_views();
+    if let Some(running) = running {
+        state.resume(running);
+    }
 
     AppLauncher::with_window(window)
+        .configure_env(ui::theme::configure_env)
         .use_simple_logger()
         .launch(state)?;
 
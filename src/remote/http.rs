@@ -0,0 +1,137 @@
+use super::{dispatch, Request, Response};
+use druid::ExtEventSink;
+use serde::Deserialize;
+use std::{collections::HashMap, error::Error, io::Read, thread};
+use tiny_http::{Header, Method, Server};
+
+/// Request bodies larger than this are rejected.
+const MAX_BODY: u64 = 64 * 1024;
+
+#[derive(Deserialize)]
+struct TopicBody {
+    action: String,
+    subject: String,
+}
+
+/// Serves the JSON API on `address`.
+///
+/// Every endpoint requires `token` as bearer token and is disabled while it is empty.
+/// No CORS headers are sent, so web pages can not read the responses.
+pub fn spawn(
+    sink: ExtEventSink,
+    address: &str,
+    token: String,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let server = Server::http(address)?;
+    log::info!("Serving the HTTP API on {}.", address);
+
+    thread::spawn(move || {
+        for mut request in server.incoming_requests() {
+            let (status, response) = route(&mut request, &sink, &token);
+            let body = serde_json::to_string(&response).unwrap_or_default();
+            let response = tiny_http::Response::from_string(body)
+                .with_status_code(status)
+                .with_header(header("Content-Type", "application/json"));
+            if let Err(err) = request.respond(response) {
+                log::warn!("Could not answer HTTP request: {}", err);
+            }
+        }
+    });
+    Ok(())
+}
+
+fn route(request: &mut tiny_http::Request, sink: &ExtEventSink, token: &str) -> (u16, Response) {
+    let url = request.url().to_string();
+    let (path, query) = match url.find('?') {
+        Some(index) => (&url[..index], parse_query(&url[index + 1..])),
+        None => (url.as_str(), HashMap::new()),
+    };
+
+    if let Err(rejection) = authorize(request, token) {
+        return rejection;
+    }
+
+    let method = request.method().clone();
+    let call = match (&method, path) {
+        (Method::Get, "/active") => Request::Status,
+        (Method::Get, "/content") => Request::Content,
+        (Method::Get, "/time-table") => Request::TimeTable,
+        (Method::Get, "/history") => Request::History {
+            from: query.get("from").cloned(),
+            to: query.get("to").cloned(),
+        },
+        (Method::Post, "/stop") => Request::Stop,
+        (Method::Post, "/start") => match read_body::<TopicBody>(request) {
+            Ok(body) => Request::Start {
+                action: body.action,
+                subject: body.subject,
+            },
+            Err(message) => return (400, Response::Error(message)),
+        },
+        _ => return (404, Response::Error(format!("No endpoint at {}.", path))),
+    };
+
+    match dispatch(sink, call) {
+        Response::Ok(value) => (200, Response::Ok(value)),
+        Response::Error(message) => (400, Response::Error(message)),
+    }
+}
+
+fn authorize(request: &tiny_http::Request, token: &str) -> Result<(), (u16, Response)> {
+    if token.is_empty() {
+        let message = "Set http_token in the settings to enable the HTTP API.";
+        return Err((403, Response::Error(message.into())));
+    }
+    let expected = format!("Bearer {}", token);
+    let authorized = request
+        .headers()
+        .iter()
+        .any(|h| h.field.equiv("Authorization") && same_secret(h.value.as_str(), &expected));
+    if authorized {
+        Ok(())
+    } else {
+        Err((
+            401,
+            Response::Error("Missing or wrong bearer token.".into()),
+        ))
+    }
+}
+
+fn read_body<T: serde::de::DeserializeOwned>(
+    request: &mut tiny_http::Request,
+) -> Result<T, String> {
+    let mut body = String::new();
+    request
+        .as_reader()
+        .take(MAX_BODY + 1)
+        .read_to_string(&mut body)
+        .map_err(|err| err.to_string())?;
+    if body.len() as u64 > MAX_BODY {
+        return Err(format!("The request body exceeds {} bytes.", MAX_BODY));
+    }
+    serde_json::from_str(&body).map_err(|err| format!("Invalid request body: {}", err))
+}
+
+/// Compares in time depending only on the length, so that the token can not be guessed byte by byte.
+fn same_secret(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter_map(|pair| {
+            let mut parts = pair.splitn(2, '=');
+            Some((parts.next()?.to_string(), parts.next()?.to_string()))
+        })
+        .collect()
+}
+
+fn header(field: &str, value: &str) -> Header {
+    Header::from_bytes(field.as_bytes(), value.as_bytes()).unwrap()
+}
@@ -0,0 +1,180 @@
+use crate::state::{AppState, DateTime, Session, SpentTime};
+use druid::{ExtEventSink, Selector, Target};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{
+    sync::{mpsc, Mutex},
+    time::Duration,
+};
+
+pub mod http;
+#[cfg(unix)]
+pub mod socket;
+
+/// Carries a [`Call`] from a remote client into the widget tree.
+pub const REQUEST: Selector<Call> = Selector::new("zeitig.remote.request");
+
+/// How long a client waits for the application to answer.
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "kebab-case")]
+pub enum Request {
+    /// Brings the tracker window to the front.
+    Show,
+    Status,
+    Content,
+    TimeTable,
+    History {
+        from: Option<String>,
+        to: Option<String>,
+    },
+    Start {
+        action: String,
+        subject: String,
+    },
+    Stop,
+    Switch {
+        action: String,
+        subject: String,
+    },
+    /// Records a past session, with the fields of the manual entry dialog.
+    Add {
+        action: String,
+        subject: String,
+        started: String,
+        ended: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Response {
+    Ok(Value),
+    Error(String),
+}
+
+impl From<Result<Value, String>> for Response {
+    fn from(result: Result<Value, String>) -> Self {
+        match result {
+            Ok(value) => Response::Ok(value),
+            Err(message) => Response::Error(message),
+        }
+    }
+}
+
+/// A request together with the channel its response is sent back on.
+pub struct Call {
+    pub request: Request,
+    reply: Mutex<mpsc::Sender<Response>>,
+}
+
+impl Call {
+    pub fn reply(&self, response: impl Into<Response>) {
+        // The client may have given up already, which is fine.
+        let _ = self.reply.lock().unwrap().send(response.into());
+    }
+}
+
+/// Hands `request` to the running application and waits for its response.
+pub fn dispatch(sink: &ExtEventSink, request: Request) -> Response {
+    let (sender, receiver) = mpsc::channel();
+    let call = Call {
+        request,
+        reply: Mutex::new(sender),
+    };
+    if sink.submit_command(REQUEST, call, Target::Auto).is_err() {
+        return Response::Error("The application is shutting down.".into());
+    }
+    receiver
+        .recv_timeout(TIMEOUT)
+        .unwrap_or_else(|_| Response::Error("The application did not respond.".into()))
+}
+
+pub fn format_datetime(dt: &DateTime) -> String {
+    dt.format("%Y-%m-%dT%H:%M:%S%z")
+}
+
+pub fn status(data: &AppState) -> Value {
+    let now = data.clock.now();
+    json!({
+        "running": data.active.is_some(),
+        "paused": data.active.as_ref().map_or(false, |s| s.is_paused()),
+        "action": data.setup.selected_action.as_ref().map(|a| a.as_ref().to_string()),
+        "subject": data.setup.selected_subject.as_ref().map(|s| s.as_ref().to_string()),
+        "started": data.active.as_ref().map(|s| format_datetime(&s.started)),
+        "session_secs": data.active.as_ref().map(|s| s.duration.as_secs()),
+        "today_secs": data.tracked_since(&now.start_of_day()).as_secs(),
+    })
+}
+
+pub fn content(data: &AppState) -> Value {
+    let actions: Vec<Value> = data
+        .content
+        .actions
+        .iter()
+        .map(|a| json!({ "id": a.id, "name": a.as_ref() }))
+        .collect();
+    let subjects: Vec<Value> = data
+        .content
+        .subjects
+        .iter()
+        .map(|s| json!({ "id": s.id, "name": s.as_ref() }))
+        .collect();
+    json!({ "actions": actions, "subjects": subjects })
+}
+
+pub fn time_table(data: &AppState) -> Value {
+    let entries: Vec<Value> = data
+        .content
+        .time_table
+        .iter()
+        .map(|(topic, time)| {
+            json!({
+                "action": topic.action.as_ref(),
+                "subject": topic.subject.as_ref(),
+                "secs": time.as_secs(),
+            })
+        })
+        .collect();
+    Value::Array(entries)
+}
+
+/// The sessions overlapping the days from `from` to `to`, both given as `YYYY-MM-DD`.
+pub fn history(data: &AppState, from: Option<&str>, to: Option<&str>) -> Result<Value, String> {
+    let now = data.clock.now();
+    let parse_day = |day: &str| {
+        DateTime::parse(&format!("{} 00:00", day), &now)
+            .ok_or_else(|| format!("Invalid day '{}', expected YYYY-MM-DD.", day))
+    };
+    let from = from.map(parse_day).transpose()?;
+    let to = to
+        .map(parse_day)
+        .transpose()?
+        .map(|day| day.after(SpentTime::from(Duration::from_secs(24 * 60 * 60))));
+
+    let sessions: Vec<Value> = data
+        .history
+        .iter()
+        .filter(|s| from.map_or(true, |from| *s.ended > *from))
+        .filter(|s| to.map_or(true, |to| *s.started < *to))
+        .map(session)
+        .collect();
+    Ok(Value::Array(sessions))
+}
+
+fn session(session: &Session) -> Value {
+    let pauses: Vec<Value> = session
+        .pauses
+        .iter()
+        .map(|p| json!({ "started": format_datetime(&p.started), "ended": format_datetime(&p.ended) }))
+        .collect();
+    json!({
+        "action": session.topic.action.as_ref(),
+        "subject": session.topic.subject.as_ref(),
+        "started": format_datetime(&session.started),
+        "ended": format_datetime(&session.ended),
+        "secs": session.duration().as_secs(),
+        "pauses": pauses,
+    })
+}
@@ -0,0 +1,67 @@
+use super::{dispatch, Request, Response};
+use crate::state::paths;
+use druid::ExtEventSink;
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    os::unix::net::{UnixListener, UnixStream},
+    thread,
+    time::Duration,
+};
+
+/// How long a client waits for the application to answer.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Listens on the control socket, answering one JSON request per line.
+pub fn spawn(sink: ExtEventSink) -> io::Result<()> {
+    let path = paths::socket_file();
+    if path.exists() {
+        std::fs::remove_file(&path)?;
+    }
+    let listener = UnixListener::bind(&path)?;
+    log::info!("Listening for remote commands on {}.", path.display());
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    let sink = sink.clone();
+                    thread::spawn(move || {
+                        if let Err(err) = serve(stream, &sink) {
+                            log::warn!("Remote connection failed: {}", err);
+                        }
+                    });
+                }
+                Err(err) => log::error!("Could not accept remote connection: {}", err),
+            }
+        }
+    });
+    Ok(())
+}
+
+fn serve(stream: UnixStream, sink: &ExtEventSink) -> io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Request>(&line) {
+            Ok(request) => dispatch(sink, request),
+            Err(err) => Response::Error(format!("Invalid request: {}", err)),
+        };
+        serde_json::to_writer(&mut writer, &response)?;
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+/// Sends `request` to the running application and waits for its response.
+pub fn send(request: &Request) -> io::Result<Response> {
+    let mut stream = UnixStream::connect(paths::socket_file())?;
+    stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+    serde_json::to_writer(&mut stream, request)?;
+    stream.write_all(b"\n")?;
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line)?;
+    Ok(serde_json::from_str(&line)?)
+}
@@ -0,0 +1,223 @@
+use super::{calendar::ColorBy, Date, History, Setup, SpentTime, Topic};
+use crate::i18n;
+use druid::{Data, Lens};
+use std::{collections::HashMap, sync::Arc};
+
+/// Restricts aggregations to the selected action or subject.
+#[derive(Clone, Copy, Data, PartialEq, Eq)]
+pub enum FilterBy {
+    All,
+    Action,
+    Subject,
+}
+
+impl Default for FilterBy {
+    fn default() -> Self {
+        FilterBy::All
+    }
+}
+
+impl FilterBy {
+    /// Whether `topic` passes the filter, given the current selection.
+    /// Without a selection for the filtered part, everything passes.
+    pub fn matches(&self, topic: &Topic, setup: &Setup) -> bool {
+        match self {
+            FilterBy::All => true,
+            FilterBy::Action => setup
+                .selected_action
+                .as_ref()
+                .map_or(true, |action| action == &topic.action),
+            FilterBy::Subject => setup
+                .selected_subject
+                .as_ref()
+                .map_or(true, |subject| subject == &topic.subject),
+        }
+    }
+}
+
+/// The time spent per day on the topics matching `filter`.
+pub fn daily_totals(
+    history: &History,
+    filter: impl Fn(&Topic) -> bool,
+) -> HashMap<time::Date, SpentTime> {
+    let mut totals: HashMap<time::Date, SpentTime> = HashMap::new();
+    for session in history.iter().filter(|s| filter(&s.topic)) {
+        for (day, spent_time) in session.days() {
+            *totals.entry(*day).or_default() += spent_time;
+        }
+    }
+    totals
+}
+
+/// Daily totals for the weeks of the last year.
+#[derive(Clone, Data)]
+pub struct HeatmapView {
+    /// The Monday of the first week shown.
+    pub first_day: Date,
+    pub last_day: Date,
+    pub days: Arc<HashMap<time::Date, SpentTime>>,
+    pub max: SpentTime,
+}
+
+impl HeatmapView {
+    pub fn generate(history: &History, filter: impl Fn(&Topic) -> bool, today: Date) -> Self {
+        let days = daily_totals(history, filter);
+        let first_day = today.add_days(-364).week_begin();
+        let max = days
+            .iter()
+            .filter(|(day, _)| **day >= *first_day && **day <= *today)
+            .map(|(_, spent_time)| *spent_time)
+            .max()
+            .unwrap_or_default();
+        HeatmapView {
+            first_day,
+            last_day: today,
+            days: Arc::new(days),
+            max,
+        }
+    }
+
+    pub fn get(&self, day: &Date) -> SpentTime {
+        self.days.get(&**day).copied().unwrap_or_default()
+    }
+}
+
+/// The length of the time spans compared in charts.
+#[derive(Clone, Copy, Data, PartialEq, Eq)]
+pub enum Period {
+    Day,
+    Week,
+    Month,
+}
+
+impl Period {
+    /// The first day of the period containing `day`.
+    pub fn begin(&self, day: Date) -> Date {
+        match self {
+            Period::Day => day,
+            Period::Week => day.week_begin(),
+            Period::Month => day.add_days(1 - day.day() as i64),
+        }
+    }
+
+    /// The first day of the period before the one starting at `begin`.
+    pub fn previous(&self, begin: Date) -> Date {
+        match self {
+            Period::Day => begin.add_days(-1),
+            Period::Week => begin.add_days(-7),
+            Period::Month => self.begin(begin.add_days(-1)),
+        }
+    }
+
+    pub fn label(&self, begin: Date) -> String {
+        match self {
+            Period::Day => i18n::short_date(&begin),
+            Period::Week => begin.format("W%V"),
+            Period::Month => format!("{} {}", i18n::month(&begin), begin.format("%y")),
+        }
+    }
+
+    /// How many periods a chart shows.
+    pub fn count(&self) -> usize {
+        match self {
+            Period::Day => 14,
+            Period::Week => 12,
+            Period::Month => 12,
+        }
+    }
+}
+
+/// What the charts show.
+#[derive(Clone, Data, Lens)]
+pub struct ChartSetup {
+    pub period: Period,
+    pub stack_by: ColorBy,
+}
+
+impl Default for ChartSetup {
+    fn default() -> Self {
+        ChartSetup {
+            period: Period::Day,
+            stack_by: ColorBy::Action,
+        }
+    }
+}
+
+/// One part of a stacked bar.
+#[derive(Clone)]
+pub struct Segment {
+    pub id: usize,
+    pub name: Arc<str>,
+    pub spent_time: SpentTime,
+}
+
+#[derive(Clone)]
+pub struct Bar {
+    pub label: String,
+    pub segments: Vec<Segment>,
+}
+
+impl Bar {
+    pub fn total(&self) -> SpentTime {
+        self.segments
+            .iter()
+            .fold(SpentTime::default(), |total, s| total + s.spent_time)
+    }
+}
+
+/// The bars of a chart, the oldest period first.
+#[derive(Clone, Data)]
+pub struct ChartView {
+    pub bars: Arc<Vec<Bar>>,
+}
+
+impl ChartView {
+    pub fn generate(history: &History, setup: &ChartSetup, today: Date) -> Self {
+        let period = setup.period;
+        let mut begins = vec![period.begin(today)];
+        while begins.len() < period.count() {
+            let previous = period.previous(*begins.last().unwrap());
+            begins.push(previous);
+        }
+        begins.reverse();
+        let first = *begins[0];
+
+        let mut buckets: HashMap<time::Date, HashMap<usize, Segment>> = HashMap::new();
+        for session in history {
+            let (id, name) = setup.stack_by.key(&session.topic);
+            for (day, spent_time) in session.days() {
+                if *day < first {
+                    continue;
+                }
+                let segment = buckets
+                    .entry(*period.begin(day))
+                    .or_default()
+                    .entry(id)
+                    .or_insert_with(|| Segment {
+                        id,
+                        name: name.into(),
+                        spent_time: SpentTime::default(),
+                    });
+                segment.spent_time += spent_time;
+            }
+        }
+
+        let bars = begins
+            .into_iter()
+            .map(|begin| {
+                let mut segments: Vec<Segment> = buckets
+                    .remove(&*begin)
+                    .map(|segments| segments.into_iter().map(|(_, s)| s).collect())
+                    .unwrap_or_default();
+                segments.sort_by(|a, b| a.name.cmp(&b.name));
+                Bar {
+                    label: period.label(begin),
+                    segments,
+                }
+            })
+            .collect();
+        ChartView {
+            bars: Arc::new(bars),
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use super::{Action, Content, History, Session, Subject, Topic};
+use super::{
+    editor::SessionEdit, Action, Content, History, RunningSession, Session, Subject, Topic,
+};
 use crate::state::SpentTime;
 use std::error::Error;
 
@@ -17,4 +19,21 @@ pub trait Backend {
 
     fn update_time(&mut self, topic: &Topic, time: &SpentTime) -> Result<(), Box<dyn Error>>;
     fn add_session(&mut self, session: &Session) -> Result<(), Box<dyn Error>>;
+    /// Adds `session` and sets the `total` of its topic, both or neither.
+    fn commit_session(
+        &mut self,
+        session: &Session,
+        total: &SpentTime,
+    ) -> Result<(), Box<dyn Error>>;
+    /// Applies `edit` together with the new `totals` of the affected topics.
+    fn edit_sessions(
+        &mut self,
+        edit: &SessionEdit,
+        totals: &[(Topic, SpentTime)],
+    ) -> Result<(), Box<dyn Error>>;
+    fn set_pinned(&mut self, topic: &Topic, pinned: bool) -> Result<(), Box<dyn Error>>;
+
+    fn load_running(&mut self, content: &Content)
+        -> Result<Option<RunningSession>, Box<dyn Error>>;
+    fn save_running(&mut self, running: Option<&RunningSession>) -> Result<(), Box<dyn Error>>;
 }
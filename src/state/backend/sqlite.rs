@@ -1,10 +1,21 @@
-use super::{Action, Backend, Content, History, Session, Subject};
-use crate::state::{DateTime, SpentTime, Topic};
-use rusqlite::{params, Connection, NO_PARAMS};
-use std::{error::Error, path::Path, time::Duration};
+use super::{Action, Backend, Content, History, RunningSession, Session, Subject};
+use crate::state::{editor::SessionEdit, DateTime, Pause, SpentTime, Topic};
+use im::Vector;
+use rusqlite::{params, Connection, OptionalExtension, NO_PARAMS};
+use std::{collections::HashMap, error::Error, path::Path, time::Duration};
 
 static SCHEMA: &str = include_str!("sqlite/schema.sql");
 
+/// The version created by `SCHEMA`.
+const VERSION: u32 = 4;
+
+/// Migrations from each version to the next one, starting at version 1.
+static MIGRATIONS: &[&str] = &[
+    include_str!("sqlite/migrations/2.sql"),
+    include_str!("sqlite/migrations/3.sql"),
+    include_str!("sqlite/migrations/4.sql"),
+];
+
 pub struct Sqlite {
     connection: Connection,
 }
@@ -12,10 +23,19 @@ pub struct Sqlite {
 impl Sqlite {
     pub fn new(path: impl AsRef<Path>) -> Result<Self, rusqlite::Error> {
         let connection = Connection::open(path)?;
+        // Lets other processes read while we write, and the other way round.
+        connection.query_row("PRAGMA journal_mode = WAL", NO_PARAMS, |_row| Ok(()))?;
 
         Ok(Sqlite { connection })
     }
 
+    /// Changes whenever another connection commits to the database,
+    /// but not on commits of this one.
+    pub fn data_version(&self) -> Result<i64, rusqlite::Error> {
+        self.connection
+            .query_row("PRAGMA data_version", NO_PARAMS, |row| row.get(0))
+    }
+
     pub fn setup(&mut self) -> Result<(), rusqlite::Error> {
         let version = self.query_version();
 
@@ -24,13 +44,23 @@ impl Sqlite {
         }
 
         match version.unwrap() {
-            1 => {}
+            VERSION => {}
+            v @ 1..=VERSION => self.migrate(v)?,
             v @ _ => log::error!("Database is using unknown version {}.", v),
         }
 
         Ok(())
     }
 
+    fn migrate(&mut self, from: u32) -> Result<(), rusqlite::Error> {
+        log::info!("Migrating database from version {} to {}.", from, VERSION);
+        let transaction = self.connection.transaction()?;
+        for migration in &MIGRATIONS[from as usize - 1..] {
+            transaction.execute_batch(migration)?;
+        }
+        transaction.commit()
+    }
+
     fn query_version(&mut self) -> Option<u32> {
         if let Ok(_) = self.connection.query_row(
             "SELECT name FROM sqlite_master WHERE type='table' AND name='Meta'",
@@ -100,9 +130,60 @@ fn add_session(connection: &Connection, session: &Session) -> Result<(), Box<dyn
             session.topic.subject.id as u32,
         ],
     )?;
+    for pause in &session.pauses {
+        connection.execute(
+            "insert into Pauses (session, started, ended) values (?, ?, ?)",
+            params![*session.started, *pause.started, *pause.ended],
+        )?;
+    }
+    Ok(())
+}
+
+fn remove_session(connection: &Connection, session: &Session) -> Result<(), Box<dyn Error>> {
+    connection.execute(
+        "delete from Pauses where session = ?",
+        params![*session.started],
+    )?;
+    connection.execute(
+        "delete from History where started = ?",
+        params![*session.started],
+    )?;
+    Ok(())
+}
+
+fn set_pinned(connection: &Connection, topic: &Topic, pinned: bool) -> Result<(), Box<dyn Error>> {
+    let query = if pinned {
+        "insert or ignore into Pinned (action, subject) values (?, ?)"
+    } else {
+        "delete from Pinned where action = ? and subject = ?"
+    };
+    connection.execute(
+        query,
+        params![topic.action.id as u32, topic.subject.id as u32],
+    )?;
     Ok(())
 }
 
+fn find_topic(
+    content: &Content,
+    action_id: usize,
+    subject_id: usize,
+) -> Result<Topic, Box<dyn Error>> {
+    let action = content.find_action(action_id).ok_or_else(|| {
+        NoneError::new(format!(
+            "An action with id {} is referenced but does not exist.",
+            action_id
+        ))
+    })?;
+    let subject = content.find_subject(subject_id).ok_or_else(|| {
+        NoneError::new(format!(
+            "A subject with id {} is referenced but does not exist.",
+            subject_id
+        ))
+    })?;
+    Ok(Topic { action, subject })
+}
+
 impl Backend for Sqlite {
     fn transfer_content(&mut self, content: &Content) -> Result<(), Box<dyn Error>> {
         let transaction = self.connection.transaction()?;
@@ -119,6 +200,10 @@ impl Backend for Sqlite {
             update_time(&transaction, topic, time)?;
         }
 
+        for topic in &content.pinned {
+            set_pinned(&transaction, topic, true)?;
+        }
+
         transaction.commit()?;
         Ok(())
     }
@@ -185,9 +270,40 @@ impl Backend for Sqlite {
             }
         }
 
+        {
+            let mut pinned_query = self
+                .connection
+                .prepare_cached("select action, subject from Pinned")?;
+            let mut rows = pinned_query.query(NO_PARAMS)?;
+            while let Some(row) = rows.next()? {
+                let action_id = row.get::<_, u32>("action")? as usize;
+                let subject_id = row.get::<_, u32>("subject")? as usize;
+                content
+                    .pinned
+                    .push_back(find_topic(&content, action_id, subject_id)?);
+            }
+        }
+
         Ok(content)
     }
     fn load_history(&mut self, content: &Content) -> Result<History, Box<dyn Error>> {
+        let mut pauses: HashMap<time::OffsetDateTime, Vector<Pause>> = HashMap::new();
+        {
+            let mut pause_query = self
+                .connection
+                .prepare_cached("select session, started, ended from Pauses order by started")?;
+            let mut rows = pause_query.query(NO_PARAMS)?;
+            while let Some(row) = rows.next()? {
+                let session: time::OffsetDateTime = row.get("session")?;
+                let started: time::OffsetDateTime = row.get("started")?;
+                let ended: time::OffsetDateTime = row.get("ended")?;
+                pauses.entry(session).or_default().push_back(Pause {
+                    started: DateTime::from(started),
+                    ended: DateTime::from(ended),
+                });
+            }
+        }
+
         let mut history = History::default();
         let mut query = self
             .connection
@@ -218,6 +334,7 @@ impl Backend for Sqlite {
                 started,
                 ended,
                 topic: Topic { action, subject },
+                pauses: pauses.remove(&started_dt).unwrap_or_default(),
             })
         }
         Ok(history)
@@ -234,6 +351,122 @@ impl Backend for Sqlite {
     fn add_session(&mut self, session: &Session) -> Result<(), Box<dyn Error>> {
         add_session(&self.connection, session)
     }
+    fn commit_session(
+        &mut self,
+        session: &Session,
+        total: &SpentTime,
+    ) -> Result<(), Box<dyn Error>> {
+        let transaction = self.connection.transaction()?;
+        add_session(&transaction, session)?;
+        update_time(&transaction, &session.topic, total)?;
+        transaction.commit()?;
+        Ok(())
+    }
+    fn edit_sessions(
+        &mut self,
+        edit: &SessionEdit,
+        totals: &[(Topic, SpentTime)],
+    ) -> Result<(), Box<dyn Error>> {
+        let transaction = self.connection.transaction()?;
+
+        for session in &edit.removed {
+            remove_session(&transaction, session)?;
+        }
+
+        for session in &edit.added {
+            add_session(&transaction, session)?;
+        }
+
+        for (topic, time) in totals {
+            update_time(&transaction, topic, time)?;
+        }
+
+        transaction.commit()?;
+        Ok(())
+    }
+    fn set_pinned(&mut self, topic: &Topic, pinned: bool) -> Result<(), Box<dyn Error>> {
+        set_pinned(&self.connection, topic, pinned)
+    }
+    fn load_running(
+        &mut self,
+        content: &Content,
+    ) -> Result<Option<RunningSession>, Box<dyn Error>> {
+        let row = self
+            .connection
+            .query_row(
+                "select started, action, subject from Running where id = 0",
+                NO_PARAMS,
+                |row| {
+                    Ok((
+                        row.get::<_, time::OffsetDateTime>("started")?,
+                        row.get::<_, u32>("action")? as usize,
+                        row.get::<_, u32>("subject")? as usize,
+                    ))
+                },
+            )
+            .optional()?;
+        let (started, action_id, subject_id) = match row {
+            Some(row) => row,
+            None => return Ok(None),
+        };
+
+        let mut pauses = Vector::new();
+        let mut paused = None;
+        let mut query = self
+            .connection
+            .prepare_cached("select started, ended from RunningPauses order by started")?;
+        let mut rows = query.query(NO_PARAMS)?;
+        while let Some(row) = rows.next()? {
+            let pause_started = DateTime::from(row.get::<_, time::OffsetDateTime>("started")?);
+            match row.get::<_, Option<time::OffsetDateTime>>("ended")? {
+                Some(ended) => pauses.push_back(Pause {
+                    started: pause_started,
+                    ended: DateTime::from(ended),
+                }),
+                None => paused = Some(pause_started),
+            }
+        }
+
+        Ok(Some(RunningSession {
+            topic: find_topic(content, action_id, subject_id)?,
+            started: DateTime::from(started),
+            pauses,
+            paused,
+        }))
+    }
+    fn save_running(&mut self, running: Option<&RunningSession>) -> Result<(), Box<dyn Error>> {
+        let transaction = self.connection.transaction()?;
+        transaction.execute("delete from RunningPauses", NO_PARAMS)?;
+        match running {
+            Some(running) => {
+                transaction.execute(
+                    "insert or replace into Running (id, started, action, subject) values (0, ?, ?, ?)",
+                    params![
+                        *running.started,
+                        running.topic.action.id as u32,
+                        running.topic.subject.id as u32,
+                    ],
+                )?;
+                for pause in &running.pauses {
+                    transaction.execute(
+                        "insert into RunningPauses (started, ended) values (?, ?)",
+                        params![*pause.started, *pause.ended],
+                    )?;
+                }
+                if let Some(paused) = running.paused {
+                    transaction.execute(
+                        "insert into RunningPauses (started, ended) values (?, null)",
+                        params![*paused],
+                    )?;
+                }
+            }
+            None => {
+                transaction.execute("delete from Running", NO_PARAMS)?;
+            }
+        };
+        transaction.commit()?;
+        Ok(())
+    }
 }
 
 pub struct NoneError {
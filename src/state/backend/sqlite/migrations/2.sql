@@ -0,0 +1,15 @@
+
+create table Running (
+    id integer check (id = 0),
+    started text not null,
+    action integer,
+    subject integer,
+
+    primary key (id),
+    foreign key (action)
+        references Actions (id),
+    foreign key (subject)
+        references Subjects (id)
+);
+
+update Meta set value = 2 where key = 'version';
@@ -0,0 +1,13 @@
+
+create table Pinned (
+    action integer,
+    subject integer,
+
+    primary key (action, subject),
+    foreign key (action)
+        references Actions (id),
+    foreign key (subject)
+        references Subjects (id)
+);
+
+update Meta set value = 3 where key = 'version';
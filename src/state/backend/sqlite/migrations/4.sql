@@ -0,0 +1,19 @@
+
+create table Pauses (
+    session text not null,
+    started text not null,
+    ended text not null,
+
+    primary key (session, started),
+    foreign key (session)
+        references History (started)
+);
+
+create table RunningPauses (
+    started text not null,
+    ended text,
+
+    primary key (started)
+);
+
+update Meta set value = 4 where key = 'version';
@@ -38,6 +38,47 @@ create table History (
         references Subjects (id)
 );
 
+create table Running (
+    id integer check (id = 0),
+    started text not null,
+    action integer,
+    subject integer,
+
+    primary key (id),
+    foreign key (action)
+        references Actions (id),
+    foreign key (subject)
+        references Subjects (id)
+);
+
+create table Pinned (
+    action integer,
+    subject integer,
+
+    primary key (action, subject),
+    foreign key (action)
+        references Actions (id),
+    foreign key (subject)
+        references Subjects (id)
+);
+
+create table Pauses (
+    session text not null,
+    started text not null,
+    ended text not null,
+
+    primary key (session, started),
+    foreign key (session)
+        references History (started)
+);
+
+create table RunningPauses (
+    started text not null,
+    ended text,
+
+    primary key (started)
+);
+
 create table Meta (
     key text,
     value text,
@@ -45,4 +86,4 @@ create table Meta (
     primary key (key)
 );
 
-insert into Meta (key, value) values ('version', 1);
+insert into Meta (key, value) values ('version', 4);
@@ -0,0 +1,81 @@
+use super::{Date, History, Session, Topic};
+use druid::{Data, Lens};
+use im::Vector;
+
+/// Which part of a topic decides the color it is drawn in.
+#[derive(Clone, Copy, Data, PartialEq, Eq)]
+pub enum ColorBy {
+    Action,
+    Subject,
+}
+
+impl ColorBy {
+    /// The id and name of the part of `topic` that is colored.
+    pub fn key<'a>(&self, topic: &'a Topic) -> (usize, &'a str) {
+        match self {
+            ColorBy::Action => (topic.action.id, topic.action.as_ref()),
+            ColorBy::Subject => (topic.subject.id, topic.subject.as_ref()),
+        }
+    }
+
+    pub fn toggled(&self) -> ColorBy {
+        match self {
+            ColorBy::Action => ColorBy::Subject,
+            ColorBy::Subject => ColorBy::Action,
+        }
+    }
+}
+
+/// The week shown in the calendar.
+#[derive(Clone, Data, Lens)]
+pub struct Calendar {
+    /// The Monday of the shown week.
+    pub week: Date,
+    pub color_by: ColorBy,
+}
+
+impl Calendar {
+    pub fn new(day: Date) -> Calendar {
+        Calendar {
+            week: day.week_begin(),
+            color_by: ColorBy::Action,
+        }
+    }
+
+    pub fn show_day(&mut self, day: Date) {
+        self.week = day.week_begin();
+    }
+
+    pub fn previous_week(&mut self) {
+        self.week = self.week.add_days(-7);
+    }
+
+    pub fn next_week(&mut self) {
+        self.week = self.week.add_days(7);
+    }
+
+    /// Collects the sessions overlapping the shown week,
+    /// with the week boundaries taken in the given offset.
+    pub fn view(&self, history: &History, offset: time::UtcOffset) -> WeekView {
+        let from = self.week.start(offset);
+        let to = self.week.add_days(7).start(offset);
+        let sessions = history
+            .iter()
+            .filter(|s| *s.started < *to && *s.ended > *from)
+            .cloned()
+            .collect();
+        WeekView {
+            begin: self.week,
+            color_by: self.color_by,
+            sessions,
+        }
+    }
+}
+
+/// Everything needed to draw one week.
+#[derive(Clone, Data)]
+pub struct WeekView {
+    pub begin: Date,
+    pub color_by: ColorBy,
+    pub sessions: Vector<Session>,
+}
@@ -0,0 +1,97 @@
+use super::DateTime;
+use druid::Data;
+use std::{ops::Deref, sync::Arc, time::Instant};
+
+/// A source for the current point in time.
+///
+/// All session logic asks the clock instead of the operating system,
+/// so that the tests can drive it with a fake clock.
+pub trait Clock: Send + Sync {
+    /// The wall-clock time, which may jump in either direction.
+    fn now(&self) -> DateTime;
+    /// A monotonic reading, only meaningful relative to other readings.
+    fn instant(&self) -> Instant;
+}
+
+/// The clock of the operating system, using the local offset.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime {
+        DateTime::from(time::OffsetDateTime::now_local())
+    }
+
+    fn instant(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A clock that only moves when it is told to.
+#[cfg(test)]
+#[derive(Clone)]
+pub struct FakeClock {
+    readings: Arc<std::sync::Mutex<(time::OffsetDateTime, Instant)>>,
+}
+
+#[cfg(test)]
+impl FakeClock {
+    pub fn new(start: DateTime) -> Self {
+        FakeClock {
+            readings: Arc::new(std::sync::Mutex::new((*start, Instant::now()))),
+        }
+    }
+
+    /// Lets time pass, moving both the wall clock and the monotonic clock.
+    pub fn advance(&self, by: std::time::Duration) {
+        let mut readings = self.readings.lock().unwrap();
+        readings.0 += by;
+        readings.1 += by;
+    }
+
+    /// Sets the wall clock without moving the monotonic clock,
+    /// like an NTP correction or a manual change of the system time.
+    pub fn set(&self, now: DateTime) {
+        self.readings.lock().unwrap().0 = *now;
+    }
+}
+
+#[cfg(test)]
+impl Clock for FakeClock {
+    fn now(&self) -> DateTime {
+        DateTime::from(self.readings.lock().unwrap().0)
+    }
+
+    fn instant(&self) -> Instant {
+        self.readings.lock().unwrap().1
+    }
+}
+
+/// A cheaply clonable handle to the clock used by the application.
+#[derive(Clone)]
+pub struct SharedClock(Arc<dyn Clock>);
+
+impl SharedClock {
+    pub fn new(clock: impl Clock + 'static) -> Self {
+        SharedClock(Arc::new(clock))
+    }
+}
+
+impl Default for SharedClock {
+    fn default() -> Self {
+        SharedClock::new(SystemClock)
+    }
+}
+
+impl Deref for SharedClock {
+    type Target = dyn Clock;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+impl Data for SharedClock {
+    fn same(&self, other: &Self) -> bool {
+        Arc::as_ptr(&self.0) as *const u8 == Arc::as_ptr(&other.0) as *const u8
+    }
+}
@@ -0,0 +1,338 @@
+use super::{Date, DateTime, History, Pause, Session, Topic};
+use crate::i18n::tr;
+use druid::{Data, Lens};
+use im::Vector;
+
+/// A change to the history, replacing some sessions with others.
+#[derive(Clone, Default)]
+pub struct SessionEdit {
+    pub removed: Vec<Session>,
+    pub added: Vec<Session>,
+}
+
+/// A session as listed in the history window.
+#[derive(Clone, Data, Lens)]
+pub struct SessionRow {
+    pub session: Session,
+    /// Whether the session shares time with another one.
+    pub overlapping: bool,
+}
+
+/// Two sessions sharing some time, the earlier one first.
+#[derive(Clone, Data)]
+pub struct Conflict {
+    pub first: Session,
+    pub second: Session,
+}
+
+/// One of the sessions of a [`Conflict`].
+#[derive(Clone, Copy, Data, PartialEq, Eq)]
+pub enum Side {
+    First,
+    Second,
+}
+
+/// How to make one side of a [`Conflict`] give way to the other one.
+#[derive(Clone, Copy, Data, PartialEq, Eq)]
+pub enum Resolution {
+    /// Cuts the overlapping part off.
+    Trim,
+    /// Cuts the other session out of the middle.
+    Split,
+    Discard,
+}
+
+/// The day shown in the history window and the session being edited.
+#[derive(Clone, Data, Lens)]
+pub struct HistoryEditor {
+    pub day: Date,
+    /// The sessions of the shown day, kept up to date by `AppState::refresh_editor`.
+    pub rows: Vector<SessionRow>,
+    pub selected: Option<Session>,
+    pub conflict: Option<Conflict>,
+    /// Where to split the selected session.
+    pub split_at: String,
+    /// The topic of the part after the split.
+    pub action: String,
+    pub subject: String,
+    pub error: String,
+}
+
+impl HistoryEditor {
+    pub fn new(day: Date) -> HistoryEditor {
+        HistoryEditor {
+            day,
+            rows: Vector::new(),
+            selected: None,
+            conflict: None,
+            split_at: String::new(),
+            action: String::new(),
+            subject: String::new(),
+            error: String::new(),
+        }
+    }
+
+    pub fn show_day(&mut self, day: Date) {
+        self.day = day;
+        self.selected = None;
+        self.conflict = None;
+        self.error.clear();
+    }
+
+    /// Selects `session`, showing its first conflict if it has one.
+    pub fn select(&mut self, session: Session, history: &History) {
+        self.conflict = history
+            .overlapping(&session)
+            .find(|other| *other.started != *session.started)
+            .map(|other| Conflict::new(&session, other));
+        self.action = session.topic.action.as_ref().to_string();
+        self.subject = session.topic.subject.as_ref().to_string();
+        self.split_at.clear();
+        self.error.clear();
+        self.selected = Some(session);
+    }
+
+    /// The sessions touching the shown day, in the order they started.
+    pub fn sessions(&self, history: &History, offset: time::UtcOffset) -> Vector<SessionRow> {
+        let from = self.day.start(offset);
+        let to = self.day.add_days(1).start(offset);
+        let mut sessions: Vec<&Session> = history
+            .iter()
+            .filter(|s| *s.started < *to && *s.ended > *from)
+            .collect();
+        sessions.sort_by_key(|s| *s.started);
+        sessions
+            .into_iter()
+            .map(|session| SessionRow {
+                overlapping: history
+                    .overlapping(session)
+                    .any(|other| *other.started != *session.started),
+                session: session.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Splits `session` at `at`, giving the part after it to `topic`.
+pub fn split(session: &Session, at: DateTime, topic: Topic) -> Result<SessionEdit, String> {
+    if *at <= *session.started || *at >= *session.ended {
+        return Err(tr("split-outside"));
+    }
+    let first = session.part(session.started, at);
+    let second = Session {
+        topic,
+        ..session.part(at, session.ended)
+    };
+    Ok(SessionEdit {
+        removed: vec![session.clone()],
+        added: vec![first, second],
+    })
+}
+
+/// Joins `first` with the `second` session following it, which has to have the
+/// same topic. A gap between them becomes a pause, so it is not counted as tracked.
+pub fn merge(first: &Session, second: &Session, history: &History) -> Result<SessionEdit, String> {
+    if first.topic != second.topic {
+        return Err(tr("merge-other-topic"));
+    }
+    if *second.started < *first.ended {
+        return Err(tr("merge-overlapping"));
+    }
+    let mut pauses = first.pauses.clone();
+    if *second.started > *first.ended {
+        pauses.push_back(Pause {
+            started: first.ended,
+            ended: second.started,
+        });
+    }
+    pauses.append(second.pauses.clone());
+    let merged = Session {
+        topic: first.topic.clone(),
+        started: first.started,
+        ended: second.ended,
+        pauses,
+    };
+    let is_part = |s: &Session| *s.started == *first.started || *s.started == *second.started;
+    if history.overlapping(&merged).any(|s| !is_part(s)) {
+        return Err(tr("merge-in-between"));
+    }
+    Ok(SessionEdit {
+        removed: vec![first.clone(), second.clone()],
+        added: vec![merged],
+    })
+}
+
+impl Conflict {
+    pub fn new(a: &Session, b: &Session) -> Conflict {
+        let (first, second) = if *a.started <= *b.started {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        Conflict {
+            first: first.clone(),
+            second: second.clone(),
+        }
+    }
+
+    pub fn session(&self, side: Side) -> &Session {
+        match side {
+            Side::First => &self.first,
+            Side::Second => &self.second,
+        }
+    }
+
+    /// Resolves the conflict by changing the session on the `yielding` side,
+    /// so that it no longer overlaps the other one.
+    pub fn resolve(&self, yielding: Side, resolution: Resolution) -> Result<SessionEdit, String> {
+        let (yielding, keep) = match yielding {
+            Side::First => (&self.first, &self.second),
+            Side::Second => (&self.second, &self.first),
+        };
+        let before = yielding.part(yielding.started, keep.started);
+        let after = yielding.part(keep.ended, yielding.ended);
+        let has_before = *yielding.started < *keep.started;
+        let has_after = *yielding.ended > *keep.ended;
+        let added = match resolution {
+            Resolution::Discard => vec![],
+            Resolution::Trim => match (has_before, has_after) {
+                (true, false) => vec![before],
+                (false, true) => vec![after],
+                (false, false) => return Err(tr("trim-within")),
+                (true, true) => return Err(tr("trim-enclosing")),
+            },
+            Resolution::Split => {
+                if !(has_before && has_after) {
+                    return Err(tr("split-not-enclosing"));
+                }
+                vec![before, after]
+            }
+        };
+        Ok(SessionEdit {
+            removed: vec![yielding.clone()],
+            added,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{merge, split, Conflict, Resolution, Side};
+    use crate::state::{
+        testing::{at, minutes, pause, session, topic},
+        History,
+    };
+
+    #[test]
+    fn split_gives_the_second_part_to_the_topic() {
+        let mut whole = session(&topic(1, 1), "2021-03-14 10:00", "2021-03-14 12:00");
+        whole
+            .pauses
+            .push_back(pause("2021-03-14 10:30", "2021-03-14 10:45"));
+
+        let edit = split(&whole, at("2021-03-14 11:00"), topic(2, 1)).unwrap();
+        assert_eq!(edit.removed.len(), 1);
+        let (first, second) = (&edit.added[0], &edit.added[1]);
+        assert!(first.topic == topic(1, 1));
+        assert_eq!(*first.ended, *at("2021-03-14 11:00"));
+        assert_eq!(first.duration(), minutes(45));
+        assert!(second.topic == topic(2, 1));
+        assert_eq!(*second.started, *at("2021-03-14 11:00"));
+        assert_eq!(second.duration(), minutes(60));
+    }
+
+    #[test]
+    fn split_has_to_lie_within_the_session() {
+        let whole = session(&topic(1, 1), "2021-03-14 10:00", "2021-03-14 12:00");
+        for at_time in &["2021-03-14 10:00", "2021-03-14 12:00", "2021-03-14 13:00"] {
+            assert!(split(&whole, at(at_time), topic(1, 1)).is_err());
+        }
+    }
+
+    #[test]
+    fn merge_keeps_the_gap_as_a_pause() {
+        let mut first = session(&topic(1, 1), "2021-03-14 10:00", "2021-03-14 11:00");
+        first
+            .pauses
+            .push_back(pause("2021-03-14 10:10", "2021-03-14 10:20"));
+        let second = session(&topic(1, 1), "2021-03-14 11:30", "2021-03-14 12:00");
+        let mut history = History::default();
+        history.add(first.clone());
+        history.add(second.clone());
+
+        let edit = merge(&first, &second, &history).unwrap();
+        assert_eq!(edit.removed.len(), 2);
+        let merged = &edit.added[0];
+        assert_eq!(*merged.started, *at("2021-03-14 10:00"));
+        assert_eq!(*merged.ended, *at("2021-03-14 12:00"));
+        assert_eq!(merged.pauses.len(), 2);
+        assert_eq!(*merged.pauses[1].started, *at("2021-03-14 11:00"));
+        assert_eq!(*merged.pauses[1].ended, *at("2021-03-14 11:30"));
+        assert_eq!(merged.duration(), first.duration() + second.duration());
+    }
+
+    #[test]
+    fn merge_rejects_other_topics_overlaps_and_sessions_in_between() {
+        let first = session(&topic(1, 1), "2021-03-14 10:00", "2021-03-14 11:00");
+        let other = session(&topic(2, 1), "2021-03-14 11:00", "2021-03-14 12:00");
+        let overlapping = session(&topic(1, 1), "2021-03-14 10:30", "2021-03-14 12:00");
+        let later = session(&topic(1, 1), "2021-03-14 13:00", "2021-03-14 14:00");
+        let between = session(&topic(2, 1), "2021-03-14 12:00", "2021-03-14 12:30");
+        let mut history = History::default();
+        for session in &[&first, &later, &between] {
+            history.add((*session).clone());
+        }
+
+        assert!(merge(&first, &other, &history).is_err());
+        assert!(merge(&first, &overlapping, &history).is_err());
+        assert!(merge(&first, &later, &history).is_err());
+    }
+
+    fn conflict() -> Conflict {
+        let outer = session(&topic(1, 1), "2021-03-14 10:00", "2021-03-14 12:00");
+        let inner = session(&topic(2, 1), "2021-03-14 11:00", "2021-03-14 11:30");
+        Conflict::new(&inner, &outer)
+    }
+
+    #[test]
+    fn resolve_splits_and_discards() {
+        let conflict = conflict();
+        assert_eq!(*conflict.first.started, *at("2021-03-14 10:00"));
+
+        let edit = conflict.resolve(Side::First, Resolution::Split).unwrap();
+        assert_eq!(edit.added.len(), 2);
+        assert_eq!(*edit.added[0].ended, *at("2021-03-14 11:00"));
+        assert_eq!(*edit.added[1].started, *at("2021-03-14 11:30"));
+
+        let edit = conflict.resolve(Side::Second, Resolution::Discard).unwrap();
+        assert!(edit.added.is_empty());
+        assert_eq!(*edit.removed[0].started, *at("2021-03-14 11:00"));
+    }
+
+    #[test]
+    fn resolve_trims_only_one_sided_overlaps() {
+        let conflict = conflict();
+        assert!(conflict.resolve(Side::First, Resolution::Trim).is_err());
+        assert!(conflict.resolve(Side::Second, Resolution::Trim).is_err());
+        assert!(conflict.resolve(Side::Second, Resolution::Split).is_err());
+
+        let early = session(&topic(1, 1), "2021-03-14 10:00", "2021-03-14 11:00");
+        let late = session(&topic(2, 1), "2021-03-14 10:30", "2021-03-14 12:00");
+        let conflict = Conflict::new(&early, &late);
+        let edit = conflict.resolve(Side::First, Resolution::Trim).unwrap();
+        assert_eq!(*edit.added[0].ended, *at("2021-03-14 10:30"));
+        let edit = conflict.resolve(Side::Second, Resolution::Trim).unwrap();
+        assert_eq!(*edit.added[0].started, *at("2021-03-14 11:00"));
+    }
+
+    #[test]
+    fn resolve_tells_sessions_starting_together_apart() {
+        let short = session(&topic(1, 1), "2021-03-14 10:00", "2021-03-14 11:00");
+        let long = session(&topic(2, 1), "2021-03-14 10:00", "2021-03-14 12:00");
+        let conflict = Conflict::new(&short, &long);
+        let edit = conflict.resolve(Side::Second, Resolution::Trim).unwrap();
+        assert!(edit.removed[0].topic == conflict.second.topic);
+        assert_eq!(*edit.added[0].started, *at("2021-03-14 11:00"));
+        assert!(conflict.resolve(Side::First, Resolution::Trim).is_err());
+    }
+}
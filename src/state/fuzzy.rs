@@ -0,0 +1,78 @@
+use druid::{Data, Lens};
+use im::Vector;
+
+/// Scores how well `pattern` matches `candidate`, or `None` if it does not.
+///
+/// The characters of the pattern have to appear in the candidate in order,
+/// ignoring case. Matches at the start of words and consecutive matches
+/// score higher, gaps between matches lower.
+pub fn score(pattern: &str, candidate: &str) -> Option<i64> {
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut last: Option<usize> = None;
+    for p in pattern
+        .to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+    {
+        let found = next + candidate[next..].iter().position(|c| *c == p)?;
+        score += 1;
+        if found == 0 || !candidate[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        match last {
+            Some(last) if found == last + 1 => score += 2,
+            Some(last) => score -= (found - last - 1).min(3) as i64,
+            None => {}
+        }
+        last = Some(found);
+        next = found + 1;
+    }
+    Some(score)
+}
+
+/// An item of a filtered list.
+#[derive(Clone, Data)]
+pub struct Candidate<T> {
+    pub item: T,
+    /// Whether the keyboard cursor is on this item.
+    pub highlighted: bool,
+}
+
+/// The filter text and keyboard cursor of a list.
+#[derive(Clone, Default, Data, Lens)]
+pub struct ListFilter {
+    pub text: String,
+    pub cursor: usize,
+}
+
+impl ListFilter {
+    /// The items matching the filter, the best match first.
+    pub fn apply<T: Clone + AsRef<str>>(&self, items: &Vector<T>) -> Vector<Candidate<T>> {
+        let mut matches: Vec<(i64, &T)> = items
+            .iter()
+            .filter_map(|item| Some((score(&self.text, item.as_ref())?, item)))
+            .collect();
+        matches.sort_by(|a, b| b.0.cmp(&a.0));
+        let cursor = self.cursor.min(matches.len().saturating_sub(1));
+        matches
+            .into_iter()
+            .enumerate()
+            .map(|(index, (_, item))| Candidate {
+                item: item.clone(),
+                highlighted: index == cursor,
+            })
+            .collect()
+    }
+
+    pub fn move_cursor(&mut self, by: isize, len: usize) {
+        let cursor = self.cursor.min(len.saturating_sub(1)) as isize + by;
+        self.cursor = cursor.max(0).min(len.saturating_sub(1) as isize) as usize;
+    }
+
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.cursor = 0;
+    }
+}
@@ -0,0 +1,60 @@
+use super::{Date, History, SpentTime, Topic};
+use druid::{Data, Lens};
+use im::Vector;
+use std::collections::{BTreeMap, HashMap};
+
+/// Summaries of the history, one per week.
+#[derive(Clone, Data, Lens)]
+pub struct Insights {
+    /// The weeks with tracked time, the most recent one first.
+    pub weeks: Vector<Week>,
+}
+
+#[derive(Clone, Data, Lens)]
+pub struct Week {
+    /// The Monday the week starts on.
+    pub begin: Date,
+    pub total: SpentTime,
+    /// The time per topic, the largest one first.
+    pub entries: Vector<Summary>,
+}
+
+#[derive(Clone, Data, Lens)]
+pub struct Summary {
+    pub topic: Topic,
+    pub spent_time: SpentTime,
+}
+
+impl Insights {
+    pub fn generate(history: &History) -> Insights {
+        let mut weeks: BTreeMap<time::Date, HashMap<Topic, SpentTime>> = BTreeMap::new();
+        for session in history {
+            for (day, spent_time) in session.days() {
+                let week = weeks.entry(*day.week_begin()).or_default();
+                *week.entry(session.topic.clone()).or_default() += spent_time;
+            }
+        }
+
+        let weeks = weeks
+            .into_iter()
+            .rev()
+            .map(|(begin, topics)| {
+                let mut entries: Vector<Summary> = topics
+                    .into_iter()
+                    .map(|(topic, spent_time)| Summary { topic, spent_time })
+                    .collect();
+                entries.sort_by(|a, b| b.spent_time.cmp(&a.spent_time));
+                let total = entries
+                    .iter()
+                    .fold(SpentTime::default(), |total, e| total + e.spent_time);
+                Week {
+                    begin: Date::from(begin),
+                    total,
+                    entries,
+                }
+            })
+            .collect();
+
+        Insights { weeks }
+    }
+}
@@ -0,0 +1,20 @@
+use super::paths;
+use fs2::FileExt;
+use std::{fs::File, io};
+
+/// Exclusive access to the data file, released when dropped or when the process exits.
+pub struct InstanceLock {
+    _file: File,
+}
+
+impl InstanceLock {
+    /// Takes the lock, or returns `None` if another process holds it.
+    pub fn acquire() -> io::Result<Option<InstanceLock>> {
+        let file = File::create(paths::lock_file())?;
+        match file.try_lock_exclusive() {
+            Ok(()) => Ok(Some(InstanceLock { _file: file })),
+            Err(err) if err.kind() == fs2::lock_contended_error().kind() => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use super::{AppState, DateTime, History, Session, SpentTime, Topic};
+use crate::i18n::{self, tr, tr_args};
+use druid::{Data, Lens};
+
+/// The fields of the dialog for adding a session after the fact.
+#[derive(Clone, Default, Data, Lens)]
+pub struct ManualEntry {
+    pub action: String,
+    pub subject: String,
+    pub started: String,
+    /// Either the end of the session or its duration.
+    pub ended: String,
+    pub error: String,
+}
+
+impl ManualEntry {
+    /// An empty entry for the selected topic.
+    pub fn new(data: &AppState) -> Self {
+        let topic = data.selected_topic();
+        ManualEntry {
+            action: topic
+                .as_ref()
+                .map(|t| t.action.as_ref().to_string())
+                .unwrap_or_default(),
+            subject: topic
+                .as_ref()
+                .map(|t| t.subject.as_ref().to_string())
+                .unwrap_or_default(),
+            ..ManualEntry::default()
+        }
+    }
+
+    /// The session described by the fields, if they are valid and the session
+    /// does not collide with any other one.
+    pub fn session(&self, data: &AppState) -> Result<Session, String> {
+        let action = data
+            .content
+            .find_action_by_name(self.action.trim())
+            .ok_or_else(|| tr_args("unknown-action", &[("name", self.action.trim().into())]))?;
+        let subject = data
+            .content
+            .find_subject_by_name(self.subject.trim())
+            .ok_or_else(|| tr_args("unknown-subject", &[("name", self.subject.trim().into())]))?;
+        let now = data.clock.now();
+        let started = DateTime::parse(&self.started, &now)
+            .ok_or_else(|| tr_args("invalid-start", &[("input", self.started.as_str().into())]))?;
+        let ended = parse_end(&self.ended, &started)
+            .ok_or_else(|| tr_args("invalid-end", &[("input", self.ended.as_str().into())]))?;
+        let session = Session {
+            topic: Topic { action, subject },
+            started,
+            ended,
+            pauses: Default::default(),
+        };
+        let running = data.active.as_ref().map(|active| active.started);
+        validate(&session, &data.history, &now, running.as_ref())?;
+        Ok(session)
+    }
+}
+
+/// Parses the end of a session that began at `started`, given either as
+/// a point in time or as a duration.
+pub fn parse_end(input: &str, started: &DateTime) -> Option<DateTime> {
+    DateTime::parse(input, started).or_else(|| Some(started.after(SpentTime::parse(input)?)))
+}
+
+/// Checks that `session` lies in the past and does not overlap any recorded
+/// session or the one running since `running`.
+pub fn validate(
+    session: &Session,
+    history: &History,
+    now: &DateTime,
+    running: Option<&DateTime>,
+) -> Result<(), String> {
+    if *session.ended <= *session.started {
+        return Err(tr("end-before-start"));
+    }
+    if *session.ended > **now {
+        return Err(tr("end-in-future"));
+    }
+    if let Some(other) = history.overlapping(session).next() {
+        let from = format!(
+            "{} {}",
+            i18n::date(&other.started.date()),
+            other.started.format("%H:%M")
+        );
+        return Err(tr_args(
+            "overlaps-session",
+            &[
+                ("action", other.topic.action.as_ref().into()),
+                ("subject", other.topic.subject.as_ref().into()),
+                ("from", from.into()),
+                ("to", other.ended.format("%H:%M").into()),
+            ],
+        ));
+    }
+    if let Some(running) = running {
+        if *session.ended > **running {
+            return Err(tr("overlaps-running"));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_end, validate};
+    use crate::state::{
+        testing::{at, session, topic},
+        History,
+    };
+
+    fn history() -> History {
+        let mut history = History::default();
+        history.add(session(
+            &topic(1, 1),
+            "2021-03-14 10:00",
+            "2021-03-14 11:00",
+        ));
+        history
+    }
+
+    #[test]
+    fn validate_accepts_free_time_in_the_past() {
+        let new = session(&topic(1, 2), "2021-03-14 11:00", "2021-03-14 12:00");
+        let now = at("2021-03-14 13:00");
+        assert!(validate(&new, &history(), &now, None).is_ok());
+        assert!(validate(&new, &history(), &now, Some(&at("2021-03-14 12:00"))).is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_sessions_ending_too_early_or_too_late() {
+        let now = at("2021-03-14 13:00");
+        let backwards = session(&topic(1, 1), "2021-03-14 12:00", "2021-03-14 11:30");
+        assert!(validate(&backwards, &history(), &now, None).is_err());
+        let empty = session(&topic(1, 1), "2021-03-14 12:00", "2021-03-14 12:00");
+        assert!(validate(&empty, &history(), &now, None).is_err());
+        let future = session(&topic(1, 1), "2021-03-14 12:00", "2021-03-14 13:30");
+        assert!(validate(&future, &history(), &now, None).is_err());
+    }
+
+    #[test]
+    fn validate_rejects_overlaps() {
+        let now = at("2021-03-14 13:00");
+        let overlapping = session(&topic(1, 2), "2021-03-14 10:30", "2021-03-14 11:30");
+        assert!(validate(&overlapping, &history(), &now, None).is_err());
+        let late = session(&topic(1, 2), "2021-03-14 11:30", "2021-03-14 12:30");
+        let running = at("2021-03-14 12:00");
+        assert!(validate(&late, &history(), &now, Some(&running)).is_err());
+    }
+
+    #[test]
+    fn parse_end_takes_a_time_or_a_duration() {
+        let started = at("2021-03-14 10:00");
+        assert_eq!(
+            *parse_end("11:15", &started).unwrap(),
+            *at("2021-03-14 11:15")
+        );
+        assert_eq!(
+            *parse_end("1h15m", &started).unwrap(),
+            *at("2021-03-14 11:15")
+        );
+        assert_eq!(
+            *parse_end("2021-03-15 09:00", &started).unwrap(),
+            *at("2021-03-15 09:00")
+        );
+        assert!(parse_end("soon", &started).is_none());
+    }
+}
@@ -0,0 +1,132 @@
+use super::{aggregate::Period, Action, AppState, Date, DateTime, SpentTime, Subject, TimeTable};
+use crate::i18n::tr_args;
+use druid::{Data, Lens};
+use std::{collections::HashMap, sync::Arc};
+
+/// The time span the matrix is computed for.
+#[derive(Clone, Copy, Data, PartialEq, Eq)]
+pub enum MatrixRange {
+    AllTime,
+    ThisWeek,
+    ThisMonth,
+    ThisYear,
+    /// From `MatrixSetup::from` to `MatrixSetup::to`, both inclusive.
+    Custom,
+}
+
+#[derive(Clone, Copy, Data, PartialEq, Eq)]
+pub enum SortBy {
+    Name,
+    Total,
+}
+
+#[derive(Clone, Data, Lens)]
+pub struct MatrixSetup {
+    pub range: MatrixRange,
+    pub from: String,
+    pub to: String,
+    pub sort_by: SortBy,
+}
+
+impl Default for MatrixSetup {
+    fn default() -> Self {
+        MatrixSetup {
+            range: MatrixRange::AllTime,
+            from: String::new(),
+            to: String::new(),
+            sort_by: SortBy::Total,
+        }
+    }
+}
+
+impl MatrixSetup {
+    /// The bounds of the range, or `None` if it covers all time.
+    pub fn bounds(&self, now: &DateTime) -> Result<Option<(DateTime, DateTime)>, String> {
+        let today = Date::from(**now);
+        let begin = match self.range {
+            MatrixRange::AllTime => return Ok(None),
+            MatrixRange::ThisWeek => Period::Week.begin(today),
+            MatrixRange::ThisMonth => Period::Month.begin(today),
+            MatrixRange::ThisYear => today.add_days(1 - today.ordinal() as i64),
+            MatrixRange::Custom => {
+                let parse_day = |day: &str| {
+                    DateTime::parse(&format!("{} 00:00", day), now)
+                        .ok_or_else(|| tr_args("invalid-day", &[("input", day.into())]))
+                };
+                let from = parse_day(self.from.as_str())?;
+                let to = parse_day(self.to.as_str())?.start_of_next_day();
+                return Ok(Some((from, to)));
+            }
+        };
+        Ok(Some((begin.start(now.offset()), *now)))
+    }
+}
+
+/// The time table as a matrix of actions and subjects, with totals.
+#[derive(Clone, Data)]
+pub struct MatrixView {
+    pub actions: Arc<Vec<(Action, SpentTime)>>,
+    pub subjects: Arc<Vec<(Subject, SpentTime)>>,
+    pub cells: Arc<HashMap<(usize, usize), SpentTime>>,
+    pub total: SpentTime,
+    pub error: Option<String>,
+}
+
+impl MatrixView {
+    pub fn generate(data: &AppState) -> MatrixView {
+        let now = data.clock.now();
+        let (time_table, error) = match data.matrix.bounds(&now) {
+            Ok(None) => (data.content.time_table.clone(), None),
+            Ok(Some((from, to))) => (data.history.time_table_between(&from, &to), None),
+            Err(err) => (TimeTable::default(), Some(err)),
+        };
+
+        let mut actions: HashMap<usize, (Action, SpentTime)> = HashMap::new();
+        let mut subjects: HashMap<usize, (Subject, SpentTime)> = HashMap::new();
+        let mut cells = HashMap::new();
+        let mut total = SpentTime::default();
+        for (topic, time) in &time_table {
+            if time.as_secs() == 0 {
+                continue;
+            }
+            let action = actions
+                .entry(topic.action.id)
+                .or_insert_with(|| (topic.action.clone(), SpentTime::default()));
+            action.1 += *time;
+            let subject = subjects
+                .entry(topic.subject.id)
+                .or_insert_with(|| (topic.subject.clone(), SpentTime::default()));
+            subject.1 += *time;
+            cells.insert((topic.action.id, topic.subject.id), *time);
+            total += *time;
+        }
+
+        let mut actions: Vec<_> = actions.into_iter().map(|(_, entry)| entry).collect();
+        let mut subjects: Vec<_> = subjects.into_iter().map(|(_, entry)| entry).collect();
+        match data.matrix.sort_by {
+            SortBy::Name => {
+                actions.sort_by(|a, b| a.0.cmp(&b.0));
+                subjects.sort_by(|a, b| a.0.cmp(&b.0));
+            }
+            SortBy::Total => {
+                actions.sort_by(|a, b| b.1.cmp(&a.1));
+                subjects.sort_by(|a, b| b.1.cmp(&a.1));
+            }
+        }
+
+        MatrixView {
+            actions: Arc::new(actions),
+            subjects: Arc::new(subjects),
+            cells: Arc::new(cells),
+            total,
+            error,
+        }
+    }
+
+    pub fn get(&self, action: &Action, subject: &Subject) -> SpentTime {
+        self.cells
+            .get(&(action.id, subject.id))
+            .copied()
+            .unwrap_or_default()
+    }
+}
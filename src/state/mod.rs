@@ -3,22 +3,312 @@ use druid_enums::Matcher;
 use im::{HashMap, Vector};
 use std::sync::Arc;
 
+pub mod aggregate;
 pub mod backend;
+pub mod calendar;
+pub mod clock;
+pub mod editor;
+pub mod fuzzy;
+pub mod insights;
+pub mod lock;
+pub mod manual;
+pub mod matrix;
+pub mod outbox;
 pub mod paths;
+pub mod settings;
+#[cfg(test)]
+pub mod testing;
 pub mod time;
 
+pub use self::calendar::Calendar;
+pub use self::clock::{Clock, SharedClock};
+pub use self::insights::Insights;
+pub use self::settings::{DurationFormat, Settings};
 pub use self::time::{Date, DateTime, SpentTime};
 
+/// How many recently used topics are offered next to the pinned ones.
+const RECENT_TOPICS: usize = 6;
+
 #[derive(Clone, Default, Data, Lens)]
 pub struct AppState {
     pub content: Content,
     pub history: History,
     pub setup: Setup,
     pub active: Option<ActiveSession>,
+    pub clock: SharedClock,
+    pub settings: Settings,
+    pub insights: Option<Insights>,
+    pub calendar: Option<Calendar>,
+    /// The sessions of the week shown in the calendar, kept while it is in use.
+    pub week: Option<calendar::WeekView>,
+    pub heatmap_filter: aggregate::FilterBy,
+    /// The heatmap of the insights, kept while they are in use.
+    pub heatmap: Option<aggregate::HeatmapView>,
+    pub chart: aggregate::ChartSetup,
+    /// The chart of the insights, kept while they are in use.
+    pub chart_view: Option<aggregate::ChartView>,
+    pub matrix: matrix::MatrixSetup,
+    /// The table of the time spent, kept while it is in use.
+    pub matrix_view: Option<matrix::MatrixView>,
+    pub manual: manual::ManualEntry,
+    pub editor: Option<editor::HistoryEditor>,
+    /// The entries of the quick switcher, kept up to date with the history and the pins.
+    pub quick: Vector<QuickTopic>,
+    /// How many pairs of sessions in the history overlap.
+    pub overlaps: usize,
+    pub backend_errors: Vector<BackendError>,
+    /// Sessions sent to the backend which it has not acknowledged yet.
+    pub pending_commits: usize,
 }
 
 #[allow(non_upper_case_globals)]
 impl AppState {
+    pub fn start_session(&mut self) {
+        self.active = Some(ActiveSession::new(&*self.clock))
+    }
+
+    /// Whether a session is active and not paused.
+    pub fn is_ticking(&self) -> bool {
+        self.active
+            .as_ref()
+            .map_or(false, |active| !active.is_paused())
+    }
+
+    pub fn tick(&mut self) {
+        if let Some(session) = self.active.as_mut() {
+            session.duration = session.elapsed(&*self.clock);
+        }
+    }
+
+    /// Ends the active session and records it, unless it was too short to be kept.
+    ///
+    /// Returns the recorded session, which still has to be sent to the backend.
+    pub fn end_session(&mut self) -> Option<Session> {
+        let mut active = self.active.take()?;
+        active.unpause(&*self.clock);
+        let duration = active.elapsed(&*self.clock);
+        if *duration <= self.settings.discard_threshold() {
+            return None;
+        }
+        let topic = Topic {
+            action: self.setup.selected_action.clone()?,
+            subject: self.setup.selected_subject.clone()?,
+        };
+        let session = Session {
+            topic,
+            started: active.started,
+            ended: active.started.after(active.gross_elapsed(&*self.clock)),
+            pauses: active.pauses,
+        };
+        self.record(session.clone());
+        Some(session)
+    }
+
+    /// Adds a finished session to the history and the totals.
+    pub fn record(&mut self, session: Session) {
+        *self.content.time_table.get_mut(session.topic.clone()) += session.duration();
+        let overlapping = self.history.overlapping(&session).count();
+        if overlapping > 0 {
+            log::warn!(
+                "The session started at {} overlaps {} other sessions.",
+                *session.started,
+                overlapping
+            );
+        }
+        self.history.add(session);
+        self.check_overlaps();
+        self.refresh_views();
+    }
+
+    pub fn check_overlaps(&mut self) {
+        self.overlaps = self.history.overlaps().len();
+    }
+
+    /// Applies `edit` to the history and the totals.
+    ///
+    /// Returns the new totals of the affected topics, which still have to be sent to the backend.
+    pub fn apply_edit(&mut self, edit: &editor::SessionEdit) -> Vec<(Topic, SpentTime)> {
+        let mut topics: Vec<Topic> = Vec::new();
+        for session in &edit.removed {
+            self.history.remove(session);
+            *self.content.time_table.get_mut(session.topic.clone()) -= session.duration();
+            topics.push(session.topic.clone());
+        }
+        for session in &edit.added {
+            self.history.add(session.clone());
+            *self.content.time_table.get_mut(session.topic.clone()) += session.duration();
+            topics.push(session.topic.clone());
+        }
+        self.check_overlaps();
+        self.refresh_views();
+
+        let mut totals: Vec<(Topic, SpentTime)> = Vec::new();
+        for topic in topics {
+            if !totals.iter().any(|(t, _)| *t == topic) {
+                let total = self.content.time_table.get(&topic);
+                totals.push((topic, total));
+            }
+        }
+        totals
+    }
+
+    /// Regenerates everything derived from the history which is in use.
+    pub fn refresh_views(&mut self) {
+        self.quick = self.quick_topics();
+        self.refresh_editor();
+        self.refresh_insights();
+        self.refresh_heatmap();
+        self.refresh_chart();
+        self.refresh_matrix();
+        self.refresh_calendar();
+    }
+
+    /// Regenerates the insights if they are in use.
+    pub fn refresh_insights(&mut self) {
+        if self.insights.is_some() {
+            self.insights = Some(Insights::generate(&self.history));
+        }
+    }
+
+    /// Regenerates the heatmap if it is in use,
+    /// after a change to the history, the filter or the selection.
+    pub fn refresh_heatmap(&mut self) {
+        if self.heatmap.is_some() {
+            self.heatmap = Some(self.heatmap_view());
+        }
+    }
+
+    /// Regenerates the chart if it is in use, after a change to the history or its setup.
+    pub fn refresh_chart(&mut self) {
+        if self.chart_view.is_some() {
+            self.chart_view = Some(self.chart_view());
+        }
+    }
+
+    pub fn chart_view(&self) -> aggregate::ChartView {
+        let today = Date::from(*self.clock.now());
+        aggregate::ChartView::generate(&self.history, &self.chart, today)
+    }
+
+    /// Regenerates the table if it is in use, after a change to the history,
+    /// its range or its order.
+    pub fn refresh_matrix(&mut self) {
+        if self.matrix_view.is_some() {
+            self.matrix_view = Some(matrix::MatrixView::generate(self));
+        }
+    }
+
+    /// Collects the sessions of the day shown in the history, if there is one.
+    pub fn refresh_editor(&mut self) {
+        let offset = self.clock.now().offset();
+        if let Some(editor) = self.editor.as_mut() {
+            editor.rows = editor.sessions(&self.history, offset);
+        }
+    }
+
+    /// Collects the sessions of the week shown in the calendar, if there is one.
+    pub fn refresh_calendar(&mut self) {
+        let offset = self.clock.now().offset();
+        self.week = self
+            .calendar
+            .as_ref()
+            .map(|calendar| calendar.view(&self.history, offset));
+    }
+
+    pub fn heatmap_view(&self) -> aggregate::HeatmapView {
+        let today = Date::from(*self.clock.now());
+        let filter = self.heatmap_filter;
+        aggregate::HeatmapView::generate(
+            &self.history,
+            |topic| filter.matches(topic, &self.setup),
+            today,
+        )
+    }
+
+    /// The active session together with its topic, as it is persisted.
+    pub fn running(&self) -> Option<RunningSession> {
+        Some(RunningSession {
+            topic: Topic {
+                action: self.setup.selected_action.clone()?,
+                subject: self.setup.selected_subject.clone()?,
+            },
+            started: self.active.as_ref()?.started,
+            pauses: self.active.as_ref()?.pauses.clone(),
+            paused: self.active.as_ref()?.paused,
+        })
+    }
+
+    /// Pauses the active session or continues it if it is paused.
+    pub fn toggle_pause(&mut self) {
+        let clock = &*self.clock;
+        if let Some(active) = self.active.as_mut() {
+            if active.is_paused() {
+                active.unpause(clock);
+            } else {
+                active.pause(clock);
+            }
+            active.duration = active.elapsed(clock);
+        }
+    }
+
+    /// Continues a session that was persisted by an earlier run.
+    pub fn resume(&mut self, running: RunningSession) {
+        self.setup.selected_action = Some(running.topic.action);
+        self.setup.selected_subject = Some(running.topic.subject);
+        self.active = Some(ActiveSession::resume(
+            running.started,
+            running.pauses,
+            running.paused,
+            &*self.clock,
+        ));
+    }
+
+    /// Replaces the content and the history with ones loaded after an outside change,
+    /// keeping the active session and the selection where possible.
+    pub fn reload(&mut self, content: Content, history: History) {
+        if let Some(action) = &self.setup.selected_action {
+            match content.find_action(action.id) {
+                Some(found) => self.setup.selected_action = Some(found),
+                None if self.active.is_some() => {
+                    log::warn!("The action of the running session has been removed.")
+                }
+                None => self.setup.selected_action = None,
+            }
+        }
+        if let Some(subject) = &self.setup.selected_subject {
+            match content.find_subject(subject.id) {
+                Some(found) => self.setup.selected_subject = Some(found),
+                None if self.active.is_some() => {
+                    log::warn!("The subject of the running session has been removed.")
+                }
+                None => self.setup.selected_subject = None,
+            }
+        }
+        if let Some(editor) = self.editor.as_mut() {
+            editor.selected = None;
+            editor.conflict = None;
+        }
+        self.content = content;
+        self.history = history;
+        self.check_overlaps();
+        self.refresh_views();
+    }
+
+    /// The time tracked from `from` until now, including the active session.
+    pub fn tracked_since(&self, from: &DateTime) -> SpentTime {
+        let now = self.clock.now();
+        let mut total = self.history.total_between(from, &now);
+        if let Some(active) = &self.active {
+            let started = std::cmp::max(*active.started, **from);
+            total += SpentTime::between(&DateTime::from(started), &now);
+            total -= Pause::total_between(&active.pauses, from, &now);
+            if let Some(paused) = active.paused {
+                total -= clipped(&paused, &now, from, &now);
+            }
+        }
+        total
+    }
+
     pub fn current_session_duration(&self) -> SpentTime {
         match (
             self.setup.selected_action.clone(),
@@ -36,6 +326,55 @@ impl AppState {
             _ => SpentTime::default(),
         }
     }
+
+    /// The selected action and subject, if both are selected.
+    pub fn selected_topic(&self) -> Option<Topic> {
+        Some(Topic {
+            action: self.setup.selected_action.clone()?,
+            subject: self.setup.selected_subject.clone()?,
+        })
+    }
+
+    /// The pinned topics followed by the most recently used ones.
+    pub fn quick_topics(&self) -> Vector<QuickTopic> {
+        let mut sessions: Vec<&Session> = self.history.iter().collect();
+        sessions.sort_by(|a, b| (*b.ended).cmp(&*a.ended));
+        let mut recent: Vec<Topic> = Vec::new();
+        for session in sessions {
+            if recent.len() == RECENT_TOPICS {
+                break;
+            }
+            if !recent.contains(&session.topic) && !self.content.pinned.contains(&session.topic) {
+                recent.push(session.topic.clone());
+            }
+        }
+
+        let pinned = self.content.pinned.iter().map(|topic| QuickTopic {
+            topic: topic.clone(),
+            pinned: true,
+        });
+        let recent = recent.into_iter().map(|topic| QuickTopic {
+            topic,
+            pinned: false,
+        });
+        pinned.chain(recent).collect()
+    }
+
+    /// Pins or unpins `topic`, returning whether it is pinned now.
+    pub fn toggle_pinned(&mut self, topic: &Topic) -> bool {
+        let pinned = match self.content.pinned.index_of(topic) {
+            Some(index) => {
+                self.content.pinned.remove(index);
+                false
+            }
+            None => {
+                self.content.pinned.push_back(topic.clone());
+                true
+            }
+        };
+        self.quick = self.quick_topics();
+        pinned
+    }
 }
 
 #[derive(Clone, Data, Lens, PartialEq, Eq, Hash)]
@@ -44,6 +383,13 @@ pub struct Topic {
     pub subject: Subject,
 }
 
+/// An entry of the quick switcher.
+#[derive(Clone, Data, Lens)]
+pub struct QuickTopic {
+    pub topic: Topic,
+    pub pinned: bool,
+}
+
 #[derive(Clone, Data, Lens, Eq, Hash)]
 pub struct Action {
     pub id: usize,
@@ -109,6 +455,7 @@ pub struct Content {
     pub actions: Vector<Action>,
     pub subjects: Vector<Subject>,
     pub time_table: TimeTable,
+    pub pinned: Vector<Topic>,
 }
 
 impl Content {
@@ -119,6 +466,14 @@ impl Content {
     pub fn find_subject(&self, id: usize) -> Option<Subject> {
         self.subjects.iter().find(|s| s.id == id).cloned()
     }
+
+    pub fn find_action_by_name(&self, name: &str) -> Option<Action> {
+        self.actions.iter().find(|a| a.as_ref() == name).cloned()
+    }
+
+    pub fn find_subject_by_name(&self, name: &str) -> Option<Subject> {
+        self.subjects.iter().find(|s| s.as_ref() == name).cloned()
+    }
 }
 
 #[derive(Clone, Default, Data)]
@@ -173,6 +528,51 @@ impl History {
     pub fn add(&mut self, session: Session) {
         self.entries.push_back(session);
     }
+
+    /// Removes the session that started at the same time as `session`.
+    pub fn remove(&mut self, session: &Session) {
+        self.entries.retain(|s| *s.started != *session.started);
+    }
+
+    /// The time spent between `from` and `to`, cutting sessions at the bounds.
+    pub fn total_between(&self, from: &DateTime, to: &DateTime) -> SpentTime {
+        self.iter().fold(SpentTime::default(), |total, s| {
+            total + s.time_between(from, to)
+        })
+    }
+
+    /// The time spent per topic between `from` and `to`.
+    pub fn time_table_between(&self, from: &DateTime, to: &DateTime) -> TimeTable {
+        let mut time_table = TimeTable::default();
+        for session in self {
+            let time = session.time_between(from, to);
+            if *time > std::time::Duration::from_secs(0) {
+                *time_table.get_mut(session.topic.clone()) += time;
+            }
+        }
+        time_table
+    }
+
+    /// All pairs of sessions sharing some time, in the order they started.
+    pub fn overlaps(&self) -> Vec<editor::Conflict> {
+        let mut sessions: Vec<&Session> = self.iter().collect();
+        sessions.sort_by_key(|s| *s.started);
+        let mut conflicts = Vec::new();
+        for (index, session) in sessions.iter().enumerate() {
+            for later in &sessions[index + 1..] {
+                if *later.started >= *session.ended {
+                    break;
+                }
+                conflicts.push(editor::Conflict::new(session, later));
+            }
+        }
+        conflicts
+    }
+
+    /// The recorded sessions sharing some time with `session`.
+    pub fn overlapping<'a>(&'a self, session: &'a Session) -> impl Iterator<Item = &'a Session> {
+        self.iter().filter(move |other| other.overlaps(session))
+    }
 }
 
 impl<'a> IntoIterator for &'a History {
@@ -189,12 +589,95 @@ pub struct Session {
     pub topic: Topic,
     pub started: DateTime,
     pub ended: DateTime,
+    pub pauses: Vector<Pause>,
 }
 
 impl Session {
+    /// The time spent, which excludes the pauses.
     pub fn duration(&self) -> SpentTime {
-        use std::convert::TryFrom;
-        SpentTime::from(std::time::Duration::try_from(*self.ended - *self.started).unwrap())
+        self.time_between(&self.started, &self.ended)
+    }
+
+    /// The part of the session between `started` and `ended`, keeping the pauses within.
+    pub fn part(&self, started: DateTime, ended: DateTime) -> Session {
+        let pauses = self
+            .pauses
+            .iter()
+            .filter(|p| *p.started < *ended && *p.ended > *started)
+            .map(|p| Pause {
+                started: DateTime::from(std::cmp::max(*p.started, *started)),
+                ended: DateTime::from(std::cmp::min(*p.ended, *ended)),
+            })
+            .collect();
+        Session {
+            topic: self.topic.clone(),
+            started,
+            ended,
+            pauses,
+        }
+    }
+
+    pub fn overlaps(&self, other: &Session) -> bool {
+        *self.started < *other.ended && *other.started < *self.ended
+    }
+
+    /// The part of the session that lies between `from` and `to`, without pauses.
+    pub fn time_between(&self, from: &DateTime, to: &DateTime) -> SpentTime {
+        let mut time = clipped(&self.started, &self.ended, from, to);
+        time -= Pause::total_between(&self.pauses, from, to);
+        time
+    }
+
+    /// The parts of the session on each day it touches, split at midnight.
+    pub fn split_days(&self) -> Vec<(DateTime, DateTime)> {
+        let mut parts = Vec::new();
+        let mut start = self.started;
+        while *start < *self.ended {
+            let midnight = start.start_of_next_day();
+            let end = if *midnight < *self.ended {
+                midnight
+            } else {
+                self.ended
+            };
+            parts.push((start, end));
+            start = end;
+        }
+        parts
+    }
+
+    /// The time spent on each day the session touches.
+    pub fn days(&self) -> Vec<(Date, SpentTime)> {
+        self.split_days()
+            .into_iter()
+            .map(|(start, end)| (Date::from(*start), self.time_between(&start, &end)))
+            .collect()
+    }
+}
+
+/// An interruption of a session, which does not count towards its duration.
+#[derive(Clone, Data, Lens)]
+pub struct Pause {
+    pub started: DateTime,
+    pub ended: DateTime,
+}
+
+impl Pause {
+    /// The time `pauses` took between `from` and `to`.
+    pub fn total_between(pauses: &Vector<Pause>, from: &DateTime, to: &DateTime) -> SpentTime {
+        pauses.iter().fold(SpentTime::default(), |total, pause| {
+            total + clipped(&pause.started, &pause.ended, from, to)
+        })
+    }
+}
+
+/// The length of the interval from `started` to `ended` that lies between `from` and `to`.
+fn clipped(started: &DateTime, ended: &DateTime, from: &DateTime, to: &DateTime) -> SpentTime {
+    let started = std::cmp::max(**started, **from);
+    let ended = std::cmp::min(**ended, **to);
+    if started < ended {
+        SpentTime::between(&DateTime::from(started), &DateTime::from(ended))
+    } else {
+        SpentTime::default()
     }
 }
 
@@ -203,23 +686,161 @@ pub struct Setup {
     pub selected_action: Option<Action>,
     pub selected_subject: Option<Subject>,
     pub creating: Creating,
+    pub action_filter: fuzzy::ListFilter,
+    pub subject_filter: fuzzy::ListFilter,
+    pub palette: bool,
+    pub palette_filter: fuzzy::ListFilter,
+    /// Why the last attempt to add an action or subject failed.
+    pub creation_error: String,
 }
 
 impl Setup {
     pub fn new_item_label(&self, _: &druid::Env) -> String {
         if self.creating == Creating::Nothing {
-            "New Item"
+            crate::i18n::tr("new-item")
         } else {
-            "Cancel"
+            crate::i18n::tr("cancel")
         }
-        .to_string()
     }
 }
 
 #[derive(Clone, Data, Lens)]
 pub struct ActiveSession {
     pub started: DateTime,
+    #[data(ignore)]
+    pub started_instant: std::time::Instant,
     pub duration: SpentTime,
+    /// The finished pauses.
+    pub pauses: Vector<Pause>,
+    /// When the current pause began, if the session is paused.
+    pub paused: Option<DateTime>,
+    #[data(ignore)]
+    pub paused_instant: Option<std::time::Instant>,
+    /// The length of the finished pauses.
+    #[data(ignore)]
+    pub paused_for: std::time::Duration,
+}
+
+impl ActiveSession {
+    pub fn new(clock: &dyn Clock) -> ActiveSession {
+        ActiveSession {
+            started: clock.now(),
+            started_instant: clock.instant(),
+            duration: SpentTime::default(),
+            pauses: Vector::new(),
+            paused: None,
+            paused_instant: None,
+            paused_for: std::time::Duration::default(),
+        }
+    }
+
+    /// Restores a session that started at `started`, going by the wall clock.
+    pub fn resume(
+        started: DateTime,
+        pauses: Vector<Pause>,
+        paused: Option<DateTime>,
+        clock: &dyn Clock,
+    ) -> ActiveSession {
+        let now = clock.now();
+        let instant = clock.instant();
+        let before = |dt: &DateTime| {
+            let ago = SpentTime::between(dt, &now);
+            instant.checked_sub(*ago).unwrap_or(instant)
+        };
+        let paused_for = *Pause::total_between(&pauses, &started, &now);
+        let mut session = ActiveSession {
+            started,
+            started_instant: before(&started),
+            duration: SpentTime::default(),
+            pauses,
+            paused,
+            paused_instant: paused.as_ref().map(before),
+            paused_for,
+        };
+        session.duration = session.elapsed(clock);
+        session
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.is_some()
+    }
+
+    pub fn pause(&mut self, clock: &dyn Clock) {
+        if !self.is_paused() {
+            self.paused = Some(clock.now());
+            self.paused_instant = Some(clock.instant());
+        }
+    }
+
+    /// Ends the current pause, if there is one.
+    pub fn unpause(&mut self, clock: &dyn Clock) {
+        if let (Some(started), Some(instant)) = (self.paused.take(), self.paused_instant.take()) {
+            let length = clock.instant().saturating_duration_since(instant);
+            self.paused_for += length;
+            self.pauses.push_back(Pause {
+                started,
+                ended: started.after(SpentTime::from(length)),
+            });
+        }
+    }
+
+    /// The time since the session started, including the pauses.
+    ///
+    /// This is measured on the monotonic clock, so it is unaffected by
+    /// changes to the wall clock while the session is running.
+    /// The wall clock is only used for the start, and the end is derived from both.
+    pub fn gross_elapsed(&self, clock: &dyn Clock) -> SpentTime {
+        SpentTime::from(
+            clock
+                .instant()
+                .saturating_duration_since(self.started_instant),
+        )
+    }
+
+    /// The time spent in the session so far, without the pauses.
+    pub fn elapsed(&self, clock: &dyn Clock) -> SpentTime {
+        let mut elapsed = self.gross_elapsed(clock);
+        elapsed -= SpentTime::from(self.paused_for);
+        if let Some(instant) = self.paused_instant {
+            elapsed -= SpentTime::from(clock.instant().saturating_duration_since(instant));
+        }
+        elapsed
+    }
+}
+
+/// A session that has not ended yet, in the form it is persisted in.
+#[derive(Clone, Data, Lens)]
+pub struct RunningSession {
+    pub topic: Topic,
+    pub started: DateTime,
+    pub pauses: Vector<Pause>,
+    pub paused: Option<DateTime>,
+}
+
+impl RunningSession {
+    /// The session as it would be recorded if it ended at `now`.
+    pub fn end(self, now: DateTime) -> Session {
+        let mut pauses = self.pauses;
+        if let Some(paused) = self.paused {
+            pauses.push_back(Pause {
+                started: paused,
+                ended: now,
+            });
+        }
+        Session {
+            topic: self.topic,
+            started: self.started,
+            ended: now,
+            pauses,
+        }
+    }
+}
+
+/// A failed write to the backend, shown until it is retried or dismissed.
+#[derive(Clone, Data, Lens)]
+pub struct BackendError {
+    pub id: usize,
+    pub message: String,
 }
 
 #[derive(Clone, Data, Matcher, PartialEq, Eq)]
@@ -236,3 +857,147 @@ impl Default for Creating {
         Creating::Nothing
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        clock::FakeClock,
+        testing::{at, minutes, pause, session, topic},
+        *,
+    };
+    use std::time::Duration;
+
+    fn tracking(start: &str) -> (AppState, FakeClock) {
+        let clock = FakeClock::new(at(start));
+        let topic = topic(1, 1);
+        let mut data = AppState {
+            clock: SharedClock::new(clock.clone()),
+            ..AppState::default()
+        };
+        data.setup.selected_action = Some(topic.action);
+        data.setup.selected_subject = Some(topic.subject);
+        (data, clock)
+    }
+
+    #[test]
+    fn end_session_records_the_elapsed_time() {
+        let (mut data, clock) = tracking("2021-03-14 10:00");
+        data.start_session();
+        clock.advance(*minutes(25));
+
+        let session = data.end_session().unwrap();
+        assert_eq!(*session.started, *at("2021-03-14 10:00"));
+        assert_eq!(*session.ended, *at("2021-03-14 10:25"));
+        assert_eq!(session.duration(), minutes(25));
+        assert!(data.active.is_none());
+        assert_eq!(data.history.iter().count(), 1);
+        assert_eq!(data.content.time_table.get(&topic(1, 1)), minutes(25));
+    }
+
+    #[test]
+    fn end_session_ignores_changes_to_the_wall_clock() {
+        let (mut data, clock) = tracking("2021-03-14 10:00");
+        data.start_session();
+        clock.advance(*minutes(10));
+        clock.set(at("2021-03-14 09:00"));
+
+        let session = data.end_session().unwrap();
+        assert_eq!(session.duration(), minutes(10));
+        assert_eq!(*session.ended, *at("2021-03-14 10:10"));
+    }
+
+    #[test]
+    fn end_session_discards_short_sessions() {
+        let (mut data, clock) = tracking("2021-03-14 10:00");
+        data.start_session();
+        clock.advance(Duration::from_secs(30));
+
+        assert!(data.end_session().is_none());
+        assert!(data.active.is_none());
+        assert_eq!(data.history.iter().count(), 0);
+
+        data.start_session();
+        clock.advance(Duration::from_secs(31));
+        assert!(data.end_session().is_some());
+    }
+
+    #[test]
+    fn end_session_splits_at_midnight() {
+        let (mut data, clock) = tracking("2021-03-14 23:50");
+        data.start_session();
+        clock.advance(*minutes(20));
+
+        let session = data.end_session().unwrap();
+        let days = session.days();
+        assert_eq!(days.len(), 2);
+        assert_eq!(*days[0].0, at("2021-03-14 00:00").date());
+        assert_eq!(days[0].1, minutes(10));
+        assert_eq!(*days[1].0, at("2021-03-15 00:00").date());
+        assert_eq!(days[1].1, minutes(10));
+    }
+
+    #[test]
+    fn pauses_do_not_count() {
+        let clock = FakeClock::new(at("2021-03-14 10:00"));
+        let mut active = ActiveSession::new(&clock);
+        clock.advance(*minutes(5));
+        active.pause(&clock);
+        clock.advance(*minutes(3));
+        assert!(active.is_paused());
+        assert_eq!(active.elapsed(&clock), minutes(5));
+
+        active.unpause(&clock);
+        clock.advance(*minutes(2));
+        assert!(!active.is_paused());
+        assert_eq!(active.elapsed(&clock), minutes(7));
+        assert_eq!(active.gross_elapsed(&clock), minutes(10));
+        assert_eq!(active.pauses.len(), 1);
+        assert_eq!(*active.pauses[0].started, *at("2021-03-14 10:05"));
+        assert_eq!(*active.pauses[0].ended, *at("2021-03-14 10:08"));
+    }
+
+    #[test]
+    fn pausing_twice_keeps_the_first_pause() {
+        let clock = FakeClock::new(at("2021-03-14 10:00"));
+        let mut active = ActiveSession::new(&clock);
+        clock.advance(*minutes(5));
+        active.pause(&clock);
+        clock.advance(*minutes(5));
+        active.pause(&clock);
+        active.unpause(&clock);
+        assert_eq!(active.elapsed(&clock), minutes(5));
+        assert_eq!(*active.pauses[0].started, *at("2021-03-14 10:05"));
+    }
+
+    #[test]
+    fn part_keeps_the_pauses_within() {
+        let mut whole = session(&topic(1, 1), "2021-03-14 10:00", "2021-03-14 12:00");
+        whole
+            .pauses
+            .push_back(pause("2021-03-14 10:30", "2021-03-14 11:30"));
+
+        let part = whole.part(at("2021-03-14 11:00"), at("2021-03-14 12:00"));
+        assert_eq!(part.pauses.len(), 1);
+        assert_eq!(*part.pauses[0].started, *at("2021-03-14 11:00"));
+        assert_eq!(*part.pauses[0].ended, *at("2021-03-14 11:30"));
+        assert_eq!(part.duration(), minutes(30));
+
+        let before = whole.part(at("2021-03-14 10:00"), at("2021-03-14 10:30"));
+        assert!(before.pauses.is_empty());
+        assert_eq!(before.duration(), minutes(30));
+    }
+
+    #[test]
+    fn pauses_are_clipped_to_the_interval() {
+        let pauses: Vector<Pause> = vec![
+            pause("2021-03-14 10:00", "2021-03-14 10:20"),
+            pause("2021-03-14 11:00", "2021-03-14 11:30"),
+        ]
+        .into_iter()
+        .collect();
+        let total = |from: &str, to: &str| Pause::total_between(&pauses, &at(from), &at(to));
+        assert_eq!(total("2021-03-14 09:00", "2021-03-14 12:00"), minutes(50));
+        assert_eq!(total("2021-03-14 10:10", "2021-03-14 11:10"), minutes(20));
+        assert_eq!(total("2021-03-14 10:20", "2021-03-14 11:00"), minutes(0));
+    }
+}
@@ -0,0 +1,222 @@
+use super::{backend::Backend, paths, Content, DateTime, History, Pause, Session, Topic};
+use serde::{Deserialize, Serialize};
+use std::{error::Error, fs};
+
+/// A point in time without loss of precision, so that it still matches
+/// the `started` key of the session once it is committed.
+#[derive(Serialize, Deserialize)]
+struct Stamp {
+    unix: i64,
+    nanos: u32,
+    offset: i32,
+}
+
+impl Stamp {
+    fn new(dt: &DateTime) -> Stamp {
+        Stamp {
+            unix: dt.unix_timestamp(),
+            nanos: dt.nanosecond(),
+            offset: dt.offset().as_seconds(),
+        }
+    }
+
+    fn datetime(&self) -> DateTime {
+        let utc = time::OffsetDateTime::from_unix_timestamp(self.unix)
+            + time::Duration::nanoseconds(self.nanos as i64);
+        DateTime::from(utc.to_offset(time::UtcOffset::seconds(self.offset)))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    action: usize,
+    subject: usize,
+    started: Stamp,
+    ended: Stamp,
+    pauses: Vec<(Stamp, Stamp)>,
+}
+
+impl Entry {
+    fn new(session: &Session) -> Entry {
+        Entry {
+            action: session.topic.action.id,
+            subject: session.topic.subject.id,
+            started: Stamp::new(&session.started),
+            ended: Stamp::new(&session.ended),
+            pauses: session
+                .pauses
+                .iter()
+                .map(|p| (Stamp::new(&p.started), Stamp::new(&p.ended)))
+                .collect(),
+        }
+    }
+
+    fn session(&self, content: &Content) -> Option<Session> {
+        Some(Session {
+            topic: Topic {
+                action: content.find_action(self.action)?,
+                subject: content.find_subject(self.subject)?,
+            },
+            started: self.started.datetime(),
+            ended: self.ended.datetime(),
+            pauses: self
+                .pauses
+                .iter()
+                .map(|(started, ended)| Pause {
+                    started: started.datetime(),
+                    ended: ended.datetime(),
+                })
+                .collect(),
+        })
+    }
+
+    fn is(&self, session: &Session) -> bool {
+        *self.started.datetime() == *session.started
+    }
+}
+
+fn read() -> Result<Vec<Entry>, Box<dyn Error>> {
+    let path = paths::outbox_file();
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+fn write(entries: &[Entry]) -> Result<(), Box<dyn Error>> {
+    let path = paths::outbox_file();
+    if entries.is_empty() {
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        return Ok(());
+    }
+    fs::write(path, serde_json::to_string(entries)?)?;
+    Ok(())
+}
+
+/// Keeps `session` for another attempt on the next start.
+pub fn push(session: &Session) -> Result<(), Box<dyn Error>> {
+    let mut entries = read()?;
+    if !entries.iter().any(|entry| entry.is(session)) {
+        entries.push(Entry::new(session));
+    }
+    write(&entries)
+}
+
+/// Forgets `session` after it has been committed.
+pub fn remove(session: &Session) -> Result<(), Box<dyn Error>> {
+    if !paths::outbox_file().exists() {
+        return Ok(());
+    }
+    let mut entries = read()?;
+    entries.retain(|entry| !entry.is(session));
+    write(&entries)
+}
+
+/// Commits the sessions left over from an earlier run and adds them to
+/// `content` and `history`. Sessions that made it to the database after all are skipped.
+pub fn replay(
+    backend: &mut dyn Backend,
+    content: &mut Content,
+    history: &mut History,
+) -> Result<(), Box<dyn Error>> {
+    let entries = read()?;
+    if entries.is_empty() {
+        return Ok(());
+    }
+    log::info!("Replaying {} sessions from the outbox.", entries.len());
+    write(&commit(entries, backend, content, history))
+}
+
+/// Commits `entries` which are not in `history` yet, returning the ones that failed.
+fn commit(
+    entries: Vec<Entry>,
+    backend: &mut dyn Backend,
+    content: &mut Content,
+    history: &mut History,
+) -> Vec<Entry> {
+    let mut remaining = Vec::new();
+    for entry in entries {
+        let session = match entry.session(content) {
+            Some(session) => session,
+            None => {
+                log::error!("Dropping a session of the outbox with an unknown topic.");
+                continue;
+            }
+        };
+        if history.iter().any(|s| *s.started == *session.started) {
+            continue;
+        }
+        let mut total = content.time_table.get(&session.topic);
+        total += session.duration();
+        match backend.commit_session(&session, &total) {
+            Ok(()) => {
+                *content.time_table.get_mut(session.topic.clone()) = total;
+                history.add(session);
+            }
+            Err(err) => {
+                log::error!("Could not replay a session of the outbox: {}", err);
+                remaining.push(entry);
+            }
+        }
+    }
+    remaining
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{commit, Entry, Stamp};
+    use crate::state::{
+        backend::{Backend, Sqlite},
+        testing::{at, minutes, pause, session},
+        Content, DateTime, History, SpentTime, Topic,
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn stamps_keep_the_exact_point_in_time() {
+        let nanos = SpentTime::from(Duration::from_nanos(123_456_789));
+        let utc = at("2021-03-14 10:00").after(nanos);
+        let dt = DateTime::from(utc.to_offset(time::UtcOffset::hours(2)));
+        let json = serde_json::to_string(&Stamp::new(&dt)).unwrap();
+        let restored = serde_json::from_str::<Stamp>(&json).unwrap().datetime();
+        assert_eq!(*restored, *dt);
+        assert_eq!(restored.offset(), dt.offset());
+        assert_eq!(restored.nanosecond(), 123_456_789);
+    }
+
+    #[test]
+    fn replayed_sessions_are_committed_once() {
+        let mut backend = Sqlite::new(":memory:").unwrap();
+        backend.setup().unwrap();
+        let topic = Topic {
+            action: backend.create_action("write").unwrap(),
+            subject: backend.create_subject("book").unwrap(),
+        };
+        let mut content = Content::default();
+        content.actions.push_back(topic.action.clone());
+        content.subjects.push_back(topic.subject.clone());
+        let mut history = History::default();
+
+        let committed = session(&topic, "2021-03-14 08:00", "2021-03-14 09:00");
+        history.add(committed.clone());
+        let mut pending = session(&topic, "2021-03-14 10:00", "2021-03-14 11:00");
+        pending
+            .pauses
+            .push_back(pause("2021-03-14 10:15", "2021-03-14 10:30"));
+        let entries = vec![
+            Entry::new(&committed),
+            Entry::new(&pending),
+            Entry::new(&pending),
+        ];
+
+        let remaining = commit(entries, &mut backend, &mut content, &mut history);
+        assert!(remaining.is_empty());
+        assert_eq!(history.iter().count(), 2);
+        assert_eq!(content.time_table.get(&topic), minutes(45));
+        let loaded = backend.load_history(&content).unwrap();
+        assert_eq!(loaded.iter().count(), 1);
+        assert_eq!(loaded.iter().next().unwrap().duration(), minutes(45));
+    }
+}
@@ -2,6 +2,11 @@ use directories::ProjectDirs;
 use std::path::{Path, PathBuf};
 
 static FILE_NAME: &str = "zeitig.db";
+static SETTINGS_FILE_NAME: &str = "settings.json";
+static SOCKET_FILE_NAME: &str = "zeitig.sock";
+static OUTBOX_FILE_NAME: &str = "outbox.json";
+static LOCK_FILE_NAME: &str = "zeitig.lock";
+static PROFILE_VARIABLE: &str = "ZEITIG_PROFILE";
 
 pub fn data_file() -> PathBuf {
     if cfg!(debug_assertions) {
@@ -16,3 +21,62 @@ pub fn data_file() -> PathBuf {
     }
     Path::new(FILE_NAME).to_owned()
 }
+
+pub fn config_dir() -> PathBuf {
+    if cfg!(debug_assertions) {
+        return PathBuf::from(".");
+    }
+    if let Some(pd) = ProjectDirs::from("", "", "Zeitig") {
+        let config = pd.config_dir();
+        if std::fs::create_dir_all(config).is_ok() {
+            return config.to_owned();
+        }
+    }
+    PathBuf::from(".")
+}
+
+/// The name of the active profile, taken from the `ZEITIG_PROFILE` variable.
+pub fn profile() -> Option<String> {
+    std::env::var(PROFILE_VARIABLE)
+        .ok()
+        .filter(|name| !name.is_empty())
+}
+
+pub fn settings_file() -> PathBuf {
+    config_dir().join(SETTINGS_FILE_NAME)
+}
+
+pub fn profile_settings_file(profile: &str) -> PathBuf {
+    config_dir()
+        .join("profiles")
+        .join(format!("{}.json", profile))
+}
+
+/// Holds the user's themes, one `<name>.json` each.
+pub fn themes_dir() -> PathBuf {
+    config_dir().join("themes")
+}
+
+pub fn socket_file() -> PathBuf {
+    if cfg!(debug_assertions) {
+        return PathBuf::from(SOCKET_FILE_NAME);
+    }
+    if let Some(pd) = ProjectDirs::from("", "", "Zeitig") {
+        if let Some(runtime) = pd.runtime_dir() {
+            if std::fs::create_dir_all(runtime).is_ok() {
+                return runtime.join(SOCKET_FILE_NAME);
+            }
+        }
+    }
+    data_file().with_file_name(SOCKET_FILE_NAME)
+}
+
+/// Sessions that could not be committed yet, next to the data file.
+pub fn outbox_file() -> PathBuf {
+    data_file().with_file_name(OUTBOX_FILE_NAME)
+}
+
+/// Held by the process working on the data file.
+pub fn lock_file() -> PathBuf {
+    data_file().with_file_name(LOCK_FILE_NAME)
+}
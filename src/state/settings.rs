@@ -0,0 +1,164 @@
+use super::paths;
+use druid::{Data, Lens};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{error::Error, fs, path::Path, time::Duration};
+
+/// User adjustable behaviour, persisted in the config directory.
+///
+/// The settings of a profile are read from `profiles/<name>.json`
+/// and override the ones from `settings.json` key by key.
+#[derive(Clone, Debug, Data, Lens, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Sessions which are not longer than this are discarded.
+    pub discard_threshold_secs: f64,
+    pub autosave_interval_secs: f64,
+    pub tick_interval_secs: f64,
+    pub window_width: f64,
+    pub window_height: f64,
+    pub http_enabled: bool,
+    pub http_address: String,
+    /// The bearer token required by every HTTP endpoint.
+    /// The HTTP API is disabled while it is empty.
+    pub http_token: String,
+    /// Like `de-DE`, or empty to use the language of the system.
+    pub language: String,
+    pub duration_format: DurationFormat,
+    /// Whether long durations are shown with whole days split off.
+    pub duration_days: bool,
+    /// `light`, `dark` or the name of a theme in the themes directory.
+    pub theme: String,
+    /// Replaces the accent color of the theme, as `#rrggbb`, unless it is empty.
+    pub accent_color: String,
+    /// Set when a settings file could not be read, so that it is not overwritten.
+    #[serde(skip)]
+    #[data(ignore)]
+    pub unreadable: bool,
+}
+
+/// How spent time is shown.
+#[derive(Clone, Copy, Debug, Data, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DurationFormat {
+    /// Like `7h 15m 0s`.
+    Units,
+    /// Like `7:15`.
+    Clock,
+    /// Like `7.25h`.
+    Decimal,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            discard_threshold_secs: 30.0,
+            autosave_interval_secs: 5.0,
+            tick_interval_secs: 1.0,
+            window_width: 300.0,
+            window_height: 400.0,
+            http_enabled: false,
+            http_address: "127.0.0.1:8787".to_string(),
+            http_token: String::new(),
+            language: String::new(),
+            duration_format: DurationFormat::Units,
+            duration_days: false,
+            theme: "dark".to_string(),
+            accent_color: String::new(),
+            unreadable: false,
+        }
+    }
+}
+
+impl Settings {
+    pub fn discard_threshold(&self) -> Duration {
+        Duration::from_secs_f64(self.discard_threshold_secs.max(0.0))
+    }
+
+    pub fn autosave_interval(&self) -> Duration {
+        Duration::from_secs_f64(self.autosave_interval_secs.max(1.0))
+    }
+
+    pub fn tick_interval(&self) -> Duration {
+        Duration::from_secs_f64(self.tick_interval_secs.max(1.0))
+    }
+
+    pub fn window_size(&self) -> (f64, f64) {
+        (self.window_width, self.window_height)
+    }
+
+    /// Loads the settings, falling back to the defaults for anything
+    /// that is missing or can not be read.
+    ///
+    /// Changes are not saved if any of the files could not be read.
+    pub fn load() -> Settings {
+        let (mut value, mut unreadable) = read_value(&paths::settings_file());
+        if let Some(profile) = paths::profile() {
+            let (overrides, broken) = read_value(&paths::profile_settings_file(&profile));
+            merge(&mut value, overrides);
+            unreadable |= broken;
+        }
+        let mut settings = serde_json::from_value(value).unwrap_or_else(|err| {
+            log::error!("Invalid settings, using the defaults instead: {}", err);
+            unreadable = true;
+            Settings::default()
+        });
+        settings.unreadable = unreadable;
+        settings
+    }
+
+    /// Writes the settings to the file of the active profile.
+    ///
+    /// A profile only receives the settings which differ from the base ones.
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        if self.unreadable {
+            log::warn!("Not saving the settings, as the settings file could not be read.");
+            return Ok(());
+        }
+        let mut value = serde_json::to_value(self)?;
+        let path = match paths::profile() {
+            Some(profile) => {
+                let mut base = serde_json::to_value(Settings::default())?;
+                merge(&mut base, read_value(&paths::settings_file()).0);
+                if let (Value::Object(own), Value::Object(base)) = (&mut value, &base) {
+                    own.retain(|key, value| base.get(key) != Some(value));
+                }
+                paths::profile_settings_file(&profile)
+            }
+            None => paths::settings_file(),
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(&value)?)?;
+        Ok(())
+    }
+}
+
+/// The content of a settings file and whether it exists but could not be read.
+fn read_value(path: &Path) -> (Value, bool) {
+    if !path.exists() {
+        return (Value::Object(Default::default()), false);
+    }
+    let parsed = fs::read_to_string(path)
+        .map_err(|err| Box::new(err) as Box<dyn Error>)
+        .and_then(|text| serde_json::from_str(&text).map_err(Into::into));
+    match parsed {
+        Ok(value) => (value, false),
+        Err(err) => {
+            log::error!("Could not read settings from {}: {}", path.display(), err);
+            (Value::Object(Default::default()), true)
+        }
+    }
+}
+
+fn merge(base: &mut Value, overrides: Value) {
+    match (base, overrides) {
+        (Value::Object(base), Value::Object(overrides)) => {
+            for (key, value) in overrides {
+                merge(base.entry(key).or_insert(Value::Null), value);
+            }
+        }
+        (base, overrides) => *base = overrides,
+    }
+}
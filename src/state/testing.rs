@@ -0,0 +1,43 @@
+//! Helpers for building the state in tests.
+use super::{Action, DateTime, Pause, Session, SpentTime, Subject, Topic};
+use std::time::Duration;
+
+/// The point in time given as `YYYY-MM-DD HH:MM`, in UTC.
+pub fn at(input: &str) -> DateTime {
+    let reference = DateTime::from(time::OffsetDateTime::unix_epoch());
+    DateTime::parse(input, &reference).unwrap()
+}
+
+pub fn minutes(minutes: u64) -> SpentTime {
+    SpentTime::from(Duration::from_secs(minutes * 60))
+}
+
+pub fn topic(action: usize, subject: usize) -> Topic {
+    Topic {
+        action: Action {
+            id: action,
+            name: format!("action {}", action).into(),
+        },
+        subject: Subject {
+            id: subject,
+            name: format!("subject {}", subject).into(),
+        },
+    }
+}
+
+/// A session of `topic` from `started` to `ended`, both given like for [`at`].
+pub fn session(topic: &Topic, started: &str, ended: &str) -> Session {
+    Session {
+        topic: topic.clone(),
+        started: at(started),
+        ended: at(ended),
+        pauses: Default::default(),
+    }
+}
+
+pub fn pause(started: &str, ended: &str) -> Pause {
+    Pause {
+        started: at(started),
+        ended: at(ended),
+    }
+}
@@ -1,7 +1,7 @@
 use druid::Data;
 use std::{
     fmt::Display,
-    ops::{Add, AddAssign, Deref, DerefMut},
+    ops::{Add, AddAssign, Deref, DerefMut, SubAssign},
     time::Duration,
 };
 
@@ -11,12 +11,36 @@ pub struct DateTime(#[data(same_fn = "PartialEq::eq")] time::OffsetDateTime);
 #[derive(Debug, Clone, Copy, Data)]
 pub struct Date(#[data(same_fn = "PartialEq::eq")] time::Date);
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct SpentTime(Duration);
 
 impl DateTime {
-    pub fn now() -> Self {
-        DateTime(time::OffsetDateTime::now_local())
+    /// The point in time after `duration` has passed.
+    pub fn after(&self, duration: SpentTime) -> DateTime {
+        DateTime(self.0 + duration.0)
+    }
+
+    /// The midnight at the begin of this day, in the same offset.
+    pub fn start_of_day(&self) -> DateTime {
+        DateTime(self.0.date().midnight().assume_offset(self.0.offset()))
+    }
+
+    /// The midnight at the end of this day, in the same offset.
+    pub fn start_of_next_day(&self) -> DateTime {
+        let next = self.0.date().next_day();
+        DateTime(next.midnight().assume_offset(self.0.offset()))
+    }
+
+    /// Parses `YYYY-MM-DD HH:MM` or `HH:MM`, where the latter refers to the day
+    /// of `reference`. The offset of `reference` is used for both.
+    pub fn parse(input: &str, reference: &DateTime) -> Option<DateTime> {
+        let input = input.trim().replace('T', " ");
+        let local = time::PrimitiveDateTime::parse(&input, "%Y-%m-%d %H:%M")
+            .or_else(|_| {
+                time::Time::parse(&input, "%H:%M").map(|time| reference.0.date().with_time(time))
+            })
+            .ok()?;
+        Some(DateTime(local.assume_offset(reference.0.offset())))
     }
 }
 
@@ -46,6 +70,23 @@ impl From<time::OffsetDateTime> for Date {
     }
 }
 
+impl Date {
+    pub fn add_days(&self, days: i64) -> Date {
+        Date(self.0 + time::Duration::days(days))
+    }
+
+    /// The midnight at the begin of this day, in the given offset.
+    pub fn start(&self, offset: time::UtcOffset) -> DateTime {
+        DateTime(self.0.midnight().assume_offset(offset))
+    }
+
+    /// The Monday of the week this day belongs to.
+    pub fn week_begin(&self) -> Date {
+        let offset = self.0.weekday().number_days_from_monday();
+        Date(self.0 - time::Duration::days(offset as i64))
+    }
+}
+
 impl Deref for Date {
     type Target = time::Date;
 
@@ -54,6 +95,49 @@ impl Deref for Date {
     }
 }
 
+impl SpentTime {
+    /// The time between `start` and `end`.
+    ///
+    /// If `end` lies before `start`, which happens when the system clock is
+    /// turned back, the interval is treated as empty instead of negative.
+    pub fn between(start: &DateTime, end: &DateTime) -> SpentTime {
+        use std::convert::TryFrom;
+        match Duration::try_from(end.0 - start.0) {
+            Ok(duration) => SpentTime(duration),
+            Err(_) => {
+                log::warn!("Interval from {} to {} is negative.", start.0, end.0);
+                SpentTime::default()
+            }
+        }
+    }
+
+    /// Parses a duration like `1h30m`, `45m`, `2h` or `1:30`.
+    pub fn parse(input: &str) -> Option<SpentTime> {
+        let input = input.trim();
+        let minutes = if let Some(index) = input.find(':') {
+            let hours: u64 = input[..index].parse().ok()?;
+            let minutes: u64 = input[index + 1..].parse().ok()?;
+            if minutes >= 60 {
+                return None;
+            }
+            hours * 60 + minutes
+        } else {
+            let (hours, rest) = match input.find('h') {
+                Some(index) => (input[..index].trim().parse().ok()?, &input[index + 1..]),
+                None => (0, input),
+            };
+            let rest = rest.trim();
+            let minutes: u64 = match rest.strip_suffix('m') {
+                Some(minutes) => minutes.trim().parse().ok()?,
+                None if rest.is_empty() && input.contains('h') => 0,
+                None => return None,
+            };
+            hours * 60 + minutes
+        };
+        Some(SpentTime(Duration::from_secs(minutes * 60)))
+    }
+}
+
 impl From<Duration> for SpentTime {
     fn from(duration: Duration) -> Self {
         SpentTime(duration)
@@ -80,6 +164,8 @@ impl DerefMut for SpentTime {
     }
 }
 
+/// A fixed format for the command line and exports, independent of the settings
+/// and the language. The user interface uses `i18n::duration` instead.
 impl Display for SpentTime {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let total = self.0.as_secs();
@@ -109,3 +195,41 @@ impl AddAssign<SpentTime> for SpentTime {
         self.0 += rhs.0;
     }
 }
+
+impl SubAssign<SpentTime> for SpentTime {
+    /// Subtracts down to zero at most.
+    fn sub_assign(&mut self, rhs: SpentTime) {
+        self.0 = self.0.checked_sub(rhs.0).unwrap_or_default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SpentTime;
+    use crate::state::testing::minutes;
+
+    #[test]
+    fn parse_durations() {
+        assert_eq!(SpentTime::parse("1h30m"), Some(minutes(90)));
+        assert_eq!(SpentTime::parse(" 1h 30m "), Some(minutes(90)));
+        assert_eq!(SpentTime::parse("45m"), Some(minutes(45)));
+        assert_eq!(SpentTime::parse("2h"), Some(minutes(120)));
+        assert_eq!(SpentTime::parse("1:30"), Some(minutes(90)));
+        assert_eq!(SpentTime::parse("0:05"), Some(minutes(5)));
+    }
+
+    #[test]
+    fn parse_rejects_invalid_durations() {
+        assert_eq!(SpentTime::parse(""), None);
+        assert_eq!(SpentTime::parse("30"), None);
+        assert_eq!(SpentTime::parse("1:60"), None);
+        assert_eq!(SpentTime::parse("1h30"), None);
+        assert_eq!(SpentTime::parse("m"), None);
+        assert_eq!(SpentTime::parse("-5m"), None);
+    }
+
+    #[test]
+    fn display_is_fixed() {
+        assert_eq!(minutes(435).to_string(), "7h 15m 0s");
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{
+    i18n::{self, tr, tr_args},
+    state::{
+        calendar::{Calendar, ColorBy},
+        AppState, Date,
+    },
+    widgets::{Maybe, WeekGrid},
+};
+use druid::{
+    widget::{Button, Flex, Label},
+    EventCtx, Widget, WidgetExt, WindowDesc,
+};
+
+/// Opens a calendar window showing the week of `day`.
+pub fn open(ctx: &mut EventCtx, data: &mut AppState, day: Date) {
+    data.calendar
+        .get_or_insert_with(|| Calendar::new(day))
+        .show_day(day);
+    data.refresh_calendar();
+    ctx.new_window(
+        WindowDesc::new(|| super::themed(ui()))
+            .title(tr("window-calendar"))
+            .window_size((700.0, 650.0)),
+    );
+}
+
+pub fn ui() -> impl Widget<AppState> {
+    Flex::column()
+        .with_child(navigation())
+        .with_spacer(5.0)
+        .with_flex_child(Maybe::or_empty(WeekGrid).lens(AppState::week), 1.0)
+        .padding(10.0)
+}
+
+fn navigation() -> impl Widget<AppState> {
+    fn update(data: &mut AppState, f: impl FnOnce(&mut Calendar, Date)) {
+        let today = Date::from(*data.clock.now());
+        if let Some(calendar) = data.calendar.as_mut() {
+            f(calendar, today);
+        }
+        data.refresh_calendar();
+    }
+    Flex::row()
+        .with_child(Button::new("<").on_click(|_, data: &mut AppState, _| {
+            update(data, |calendar, _| calendar.previous_week())
+        }))
+        .with_spacer(5.0)
+        .with_child(
+            Button::new(tr("today")).on_click(|_, data: &mut AppState, _| {
+                update(data, |calendar, today| calendar.show_day(today))
+            }),
+        )
+        .with_spacer(5.0)
+        .with_child(
+            Button::new(">").on_click(|_, data: &mut AppState, _| {
+                update(data, |calendar, _| calendar.next_week())
+            }),
+        )
+        .with_spacer(10.0)
+        .with_flex_child(
+            Label::dynamic(|data: &AppState, _| match &data.calendar {
+                Some(calendar) => {
+                    tr_args("week-of", &[("date", i18n::date(&calendar.week).into())])
+                }
+                None => String::new(),
+            })
+            .expand_width(),
+            1.0,
+        )
+        .with_child(
+            Button::dynamic(|data: &AppState, _| match &data.calendar {
+                Some(calendar) if calendar.color_by == ColorBy::Action => tr("color-by-subject"),
+                _ => tr("color-by-action"),
+            })
+            .on_click(|_, data: &mut AppState, _| {
+                update(data, |calendar, _| {
+                    calendar.color_by = calendar.color_by.toggled()
+                })
+            }),
+        )
+}
@@ -0,0 +1,314 @@
+use crate::{
+    controller::{backend_msg, CommandReceiver},
+    i18n::{self, tr, tr_args},
+    state::{
+        editor::{self, Conflict, HistoryEditor, Resolution, SessionEdit, SessionRow, Side},
+        AppState, Date, DateTime, Session, Topic,
+    },
+    widgets::Maybe,
+};
+use druid::{
+    widget::{Button, CrossAxisAlignment, Either, Flex, Label, List, Painter, Scroll, TextBox},
+    Color, Command, Env, EventCtx, RenderContext, Selector, Target, Widget, WidgetExt, WindowDesc,
+};
+
+const SELECT: Selector<Session> = Selector::new("zeitig.history.select");
+const MERGE_NEXT: Selector<Session> = Selector::new("zeitig.history.merge-next");
+const SPLIT: Selector = Selector::new("zeitig.history.split");
+/// Makes the given side of the shown conflict give way to the other one.
+const RESOLVE: Selector<(Side, Resolution)> = Selector::new("zeitig.history.resolve");
+const FIND_OVERLAP: Selector = Selector::new("zeitig.history.find-overlap");
+
+const OVERLAP_COLOR: Color = Color::rgba8(0xd0, 0x30, 0x30, 0x60);
+
+/// Opens the history window showing the first overlap in the history.
+pub fn open_overlaps(ctx: &mut EventCtx, data: &mut AppState) {
+    let today = Date::from(*data.clock.now());
+    open(ctx, data, today);
+    show_first_overlap(data);
+}
+
+/// Opens the history window showing the sessions of `day`.
+pub fn open(ctx: &mut EventCtx, data: &mut AppState, day: Date) {
+    data.editor
+        .get_or_insert_with(|| HistoryEditor::new(day))
+        .show_day(day);
+    data.refresh_editor();
+    ctx.new_window(
+        WindowDesc::new(|| super::themed(ui()))
+            .title(tr("window-history"))
+            .window_size((450.0, 500.0)),
+    );
+}
+
+/// Applies `edit` and sends it to the backend, or shows why it is not possible.
+fn apply(ctx: &mut EventCtx, data: &mut AppState, edit: Result<SessionEdit, String>) {
+    match edit {
+        Ok(edit) => {
+            let totals = data.apply_edit(&edit);
+            ctx.submit_command(
+                backend_msg::EDIT_SESSIONS
+                    .with((edit, totals))
+                    .to(Target::Global),
+            );
+            if let Some(editor) = data.editor.as_mut() {
+                editor.selected = None;
+                editor.conflict = None;
+                editor.error.clear();
+            }
+        }
+        Err(message) => {
+            if let Some(editor) = data.editor.as_mut() {
+                editor.error = message;
+            }
+        }
+    }
+}
+
+fn split(data: &AppState) -> Result<SessionEdit, String> {
+    let editor = data.editor.as_ref().ok_or_else(|| tr("no-history"))?;
+    let session = editor.selected.as_ref().ok_or_else(|| tr("no-selection"))?;
+    let at = DateTime::parse(&editor.split_at, &session.started).ok_or_else(|| {
+        tr_args(
+            "invalid-time",
+            &[("input", editor.split_at.as_str().into())],
+        )
+    })?;
+    let action = data
+        .content
+        .find_action_by_name(editor.action.trim())
+        .ok_or_else(|| tr_args("unknown-action", &[("name", editor.action.trim().into())]))?;
+    let subject = data
+        .content
+        .find_subject_by_name(editor.subject.trim())
+        .ok_or_else(|| tr_args("unknown-subject", &[("name", editor.subject.trim().into())]))?;
+    editor::split(session, at, Topic { action, subject })
+}
+
+fn merge_next(data: &AppState, session: &Session) -> Result<SessionEdit, String> {
+    let editor = data.editor.as_ref().ok_or_else(|| tr("no-history"))?;
+    let next = editor
+        .rows
+        .iter()
+        .skip_while(|row| *row.session.started != *session.started)
+        .nth(1)
+        .ok_or_else(|| tr("no-later-session"))?;
+    editor::merge(session, &next.session, &data.history)
+}
+
+fn resolve(data: &AppState, yielding: Side, resolution: Resolution) -> Result<SessionEdit, String> {
+    let editor = data.editor.as_ref().ok_or_else(|| tr("no-history"))?;
+    let conflict = editor.conflict.as_ref().ok_or_else(|| tr("no-conflict"))?;
+    conflict.resolve(yielding, resolution)
+}
+
+fn show_first_overlap(data: &mut AppState) {
+    let first = data.history.overlaps().into_iter().next();
+    let offset = data.clock.now().offset();
+    if let Some(editor) = data.editor.as_mut() {
+        match first {
+            Some(conflict) => {
+                let day = Date::from(conflict.first.started.to_offset(offset));
+                editor.show_day(day);
+                editor.conflict = Some(conflict);
+            }
+            None => editor.error = tr("no-overlaps"),
+        }
+    }
+    data.refresh_editor();
+}
+
+fn handle_command(ctx: &mut EventCtx, data: &mut AppState, cmd: &Command) {
+    if let Some(session) = cmd.get(SELECT) {
+        if let Some(editor) = data.editor.as_mut() {
+            editor.select(session.clone(), &data.history);
+        }
+    }
+    if let Some((side, resolution)) = cmd.get(RESOLVE) {
+        let edit = resolve(data, *side, *resolution);
+        apply(ctx, data, edit);
+    }
+    if cmd.is(FIND_OVERLAP) {
+        show_first_overlap(data);
+    }
+    if cmd.is(SPLIT) {
+        let edit = split(data);
+        apply(ctx, data, edit);
+    }
+    if let Some(session) = cmd.get(MERGE_NEXT) {
+        let edit = merge_next(data, session);
+        apply(ctx, data, edit);
+    }
+}
+
+pub fn ui() -> impl Widget<AppState> {
+    Flex::column()
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .with_child(navigation())
+        .with_spacer(5.0)
+        .with_flex_child(
+            Maybe::or_empty(
+                Scroll::new(List::new(session_row))
+                    .vertical()
+                    .lens(HistoryEditor::rows),
+            )
+            .lens(AppState::editor),
+            1.0,
+        )
+        .with_spacer(5.0)
+        .with_child(Maybe::or_empty(conflict()).lens(AppState::editor))
+        .with_child(Maybe::or_empty(splitter()).lens(AppState::editor))
+        .padding(10.0)
+        .controller(CommandReceiver::new(handle_command))
+}
+
+fn navigation() -> impl Widget<AppState> {
+    fn update(data: &mut AppState, f: impl FnOnce(&mut HistoryEditor, Date)) {
+        let today = Date::from(*data.clock.now());
+        if let Some(editor) = data.editor.as_mut() {
+            f(editor, today);
+        }
+        data.refresh_editor();
+    }
+    Flex::row()
+        .with_child(Button::new("<").on_click(|_, data: &mut AppState, _| {
+            update(data, |editor, _| editor.show_day(editor.day.add_days(-1)))
+        }))
+        .with_spacer(5.0)
+        .with_child(
+            Button::new(tr("today")).on_click(|_, data: &mut AppState, _| {
+                update(data, |editor, today| editor.show_day(today))
+            }),
+        )
+        .with_spacer(5.0)
+        .with_child(Button::new(">").on_click(|_, data: &mut AppState, _| {
+            update(data, |editor, _| editor.show_day(editor.day.add_days(1)))
+        }))
+        .with_spacer(10.0)
+        .with_flex_child(
+            Label::dynamic(|data: &AppState, _| match &data.editor {
+                Some(editor) => format!(
+                    "{}, {}",
+                    i18n::weekday(&editor.day),
+                    i18n::date(&editor.day)
+                ),
+                None => String::new(),
+            })
+            .expand_width(),
+            1.0,
+        )
+        .with_child(
+            Button::new(tr("find-overlaps")).on_click(|ctx, _, _| ctx.submit_command(FIND_OVERLAP)),
+        )
+}
+
+fn describe(session: &Session, env: &Env) -> String {
+    format!(
+        "{} - {}  {}  {} {}",
+        session.started.format("%H:%M"),
+        session.ended.format("%H:%M"),
+        i18n::duration(&session.duration(), env),
+        session.topic.action.as_ref(),
+        session.topic.subject.as_ref(),
+    )
+}
+
+/// A session of the shown day, highlighted if it overlaps another one.
+fn session_row() -> impl Widget<SessionRow> {
+    Flex::row()
+        .with_flex_child(
+            Label::dynamic(|row: &SessionRow, env: &Env| describe(&row.session, env))
+                .expand_width()
+                .on_click(|ctx, row: &mut SessionRow, _| {
+                    ctx.submit_command(SELECT.with(row.session.clone()))
+                }),
+            1.0,
+        )
+        .with_child(
+            Button::new(tr("merge-with-next")).on_click(|ctx, row: &mut SessionRow, _| {
+                ctx.submit_command(MERGE_NEXT.with(row.session.clone()))
+            }),
+        )
+        .padding((0.0, 2.0))
+        .background(Painter::new(|ctx, row: &SessionRow, _| {
+            if row.overlapping {
+                let bounds = ctx.size().to_rect();
+                ctx.fill(bounds, &OVERLAP_COLOR);
+            }
+        }))
+}
+
+/// The resolutions for one side of the shown conflict.
+fn resolutions(side: Side) -> impl Widget<Conflict> {
+    let button = move |label: &str, resolution: Resolution| {
+        Button::new(tr(label)).on_click(move |ctx, _: &mut Conflict, _| {
+            ctx.submit_command(RESOLVE.with((side, resolution)))
+        })
+    };
+    Flex::row()
+        .with_flex_child(
+            Label::dynamic(move |conflict: &Conflict, env: &Env| {
+                describe(conflict.session(side), env)
+            })
+            .expand_width(),
+            1.0,
+        )
+        .with_child(button("trim", Resolution::Trim))
+        .with_child(button("split", Resolution::Split))
+        .with_child(button("discard", Resolution::Discard))
+}
+
+fn conflict() -> impl Widget<HistoryEditor> {
+    Maybe::or_empty(
+        Flex::column()
+            .cross_axis_alignment(CrossAxisAlignment::Start)
+            .with_child(Label::new(tr("sessions-overlap")))
+            .with_child(resolutions(Side::First))
+            .with_child(resolutions(Side::Second))
+            .padding((0.0, 0.0, 0.0, 10.0)),
+    )
+    .lens(HistoryEditor::conflict)
+}
+
+fn splitter() -> impl Widget<HistoryEditor> {
+    Flex::column()
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .with_child(Either::new(
+            |editor: &HistoryEditor, _| editor.selected.is_some(),
+            Flex::column()
+                .cross_axis_alignment(CrossAxisAlignment::Start)
+                .with_child(Label::dynamic(
+                    |editor: &HistoryEditor, env: &Env| match &editor.selected {
+                        Some(session) => tr_args(
+                            "split-session",
+                            &[("session", describe(session, env).into())],
+                        ),
+                        None => String::new(),
+                    },
+                ))
+                .with_spacer(5.0)
+                .with_child(
+                    Flex::row()
+                        .with_child(Label::new(tr("split-at")))
+                        .with_spacer(3.0)
+                        .with_child(
+                            TextBox::new()
+                                .with_placeholder(tr("split-placeholder"))
+                                .lens(HistoryEditor::split_at),
+                        )
+                        .with_spacer(5.0)
+                        .with_child(Label::new(tr("continuing-with")))
+                        .with_spacer(3.0)
+                        .with_child(TextBox::new().lens(HistoryEditor::action))
+                        .with_child(TextBox::new().lens(HistoryEditor::subject)),
+                )
+                .with_spacer(5.0)
+                .with_child(
+                    Button::new(tr("split")).on_click(|ctx, _, _| ctx.submit_command(SPLIT)),
+                ),
+            Label::new(tr("click-to-split")),
+        ))
+        .with_child(Label::dynamic(|editor: &HistoryEditor, _| {
+            editor.error.clone()
+        }))
+}
@@ -1,37 +1,130 @@
 use druid::{
-    widget::{CrossAxisAlignment, Flex, Label, List, Scroll},
-    Widget, WidgetExt,
+    lens,
+    widget::{CrossAxisAlignment, Flex, Label, List, RadioGroup, Scroll},
+    Command, Data, Env, EventCtx, Widget, WidgetExt,
 };
 
 use crate::{
+    controller::CommandReceiver,
+    i18n::{self, tr, tr_args},
     state::{
+        aggregate::{ChartSetup, FilterBy, Period},
+        calendar::ColorBy,
         insights::{Insights, Summary, Week},
         AppState,
     },
-    widgets::Maybe,
+    ui,
+    widgets::{heatmap, BarChart, Heatmap, Maybe},
 };
 
-#[allow(dead_code)]
 pub fn ui() -> impl Widget<AppState> {
-    Maybe::or_empty(inner_ui()).lens(AppState::insights)
+    Flex::column()
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .with_child(activity())
+        .with_spacer(10.0)
+        .with_child(charts())
+        .with_spacer(10.0)
+        .with_flex_child(Maybe::or_empty(weeks()).lens(AppState::insights), 1.0)
+        .padding(10.0)
+        .controller(CommandReceiver::new(handle_command))
 }
 
-fn inner_ui() -> impl Widget<Insights> {
+fn handle_command(ctx: &mut EventCtx, data: &mut AppState, cmd: &Command) {
+    if let Some(day) = cmd.get(heatmap::DAY_CLICKED) {
+        ui::calendar::open(ctx, data, *day);
+    }
+}
+
+fn activity() -> impl Widget<AppState> {
+    Flex::column()
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .with_child(
+            Flex::row()
+                .with_child(Label::new(tr("activity")))
+                .with_spacer(10.0)
+                .with_child(
+                    RadioGroup::new(vec![
+                        (tr("filter-all"), FilterBy::All),
+                        (tr("filter-action"), FilterBy::Action),
+                        (tr("filter-subject"), FilterBy::Subject),
+                    ])
+                    .lens(lens::Map::new(
+                        |data: &AppState| data.heatmap_filter,
+                        |data: &mut AppState, filter: FilterBy| {
+                            if data.heatmap_filter != filter {
+                                data.heatmap_filter = filter;
+                                data.refresh_heatmap();
+                            }
+                        },
+                    )),
+                ),
+        )
+        .with_spacer(5.0)
+        .with_child(Maybe::or_empty(Scroll::new(Heatmap).horizontal()).lens(AppState::heatmap))
+}
+
+fn charts() -> impl Widget<AppState> {
+    Flex::column()
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .with_child(
+            Flex::row()
+                .cross_axis_alignment(CrossAxisAlignment::Start)
+                .with_child(Label::new(tr("time-per")))
+                .with_spacer(10.0)
+                .with_child(
+                    RadioGroup::new(vec![
+                        (tr("period-day"), Period::Day),
+                        (tr("period-week"), Period::Week),
+                        (tr("period-month"), Period::Month),
+                    ])
+                    .lens(ChartSetup::period),
+                )
+                .with_spacer(10.0)
+                .with_child(Label::new(tr("stack-by")))
+                .with_spacer(10.0)
+                .with_child(
+                    RadioGroup::new(vec![
+                        (tr("action"), ColorBy::Action),
+                        (tr("subject"), ColorBy::Subject),
+                    ])
+                    .lens(ChartSetup::stack_by),
+                )
+                .lens(lens::Map::new(
+                    |data: &AppState| data.chart.clone(),
+                    |data: &mut AppState, chart: ChartSetup| {
+                        if !data.chart.same(&chart) {
+                            data.chart = chart;
+                            data.refresh_chart();
+                        }
+                    },
+                )),
+        )
+        .with_spacer(5.0)
+        .with_child(Maybe::or_empty(BarChart.expand_width()).lens(AppState::chart_view))
+}
+
+fn weeks() -> impl Widget<Insights> {
     Scroll::new(List::new(|| {
         Flex::column()
             .cross_axis_alignment(CrossAxisAlignment::Start)
-            .with_child(Label::dynamic(|week: &Week, _| {
-                format!("Week {}", *week.begin)
+            .with_child(Label::dynamic(|week: &Week, env: &Env| {
+                tr_args(
+                    "week-summary",
+                    &[
+                        ("date", i18n::date(&week.begin).into()),
+                        ("duration", i18n::duration(&week.total, env).into()),
+                    ],
+                )
             }))
             .with_spacer(3.0)
             .with_child(
                 List::new(|| {
-                    Label::dynamic(|sum: &Summary, _| {
+                    Label::dynamic(|sum: &Summary, env: &Env| {
                         format!(
                             "    {} {}: {}",
                             sum.topic.action.as_ref(),
                             sum.topic.subject.as_ref(),
-                            sum.spent_time
+                            i18n::duration(&sum.spent_time, env)
                         )
                     })
                 })
@@ -39,5 +132,6 @@ fn inner_ui() -> impl Widget<Insights> {
             )
             .with_spacer(10.0)
     }))
+    .vertical()
     .lens(Insights::weeks)
 }
@@ -0,0 +1,48 @@
+use crate::{
+    controller::backend_msg,
+    i18n::tr,
+    state::{manual::ManualEntry, AppState},
+};
+use druid::{
+    commands::CLOSE_WINDOW,
+    widget::{Button, CrossAxisAlignment, Flex, Label, TextBox},
+    EventCtx, Target, Widget, WidgetExt,
+};
+
+fn add(ctx: &mut EventCtx, data: &mut AppState) {
+    match data.manual.session(data) {
+        Ok(session) => {
+            data.record(session.clone());
+            ctx.submit_command(backend_msg::ADD_SESSION.with(session).to(Target::Global));
+            ctx.submit_command(CLOSE_WINDOW);
+        }
+        Err(message) => data.manual.error = message,
+    }
+}
+
+fn field(label: &str, placeholder: String) -> Flex<String> {
+    Flex::column()
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .with_child(Label::new(tr(label)))
+        .with_child(TextBox::new().with_placeholder(placeholder).expand_width())
+        .with_spacer(5.0)
+}
+
+pub fn ui() -> impl Widget<AppState> {
+    Flex::column()
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .with_child(
+            Flex::column()
+                .with_child(field("action", String::new()).lens(ManualEntry::action))
+                .with_child(field("subject", String::new()).lens(ManualEntry::subject))
+                .with_child(
+                    field("started-at", tr("started-placeholder")).lens(ManualEntry::started),
+                )
+                .with_child(field("ended-at", tr("ended-placeholder")).lens(ManualEntry::ended))
+                .with_child(Label::dynamic(|entry: &ManualEntry, _| entry.error.clone()))
+                .lens(AppState::manual),
+        )
+        .with_spacer(5.0)
+        .with_child(Button::new(tr("add")).on_click(|ctx, data: &mut AppState, _| add(ctx, data)))
+        .padding(10.0)
+}
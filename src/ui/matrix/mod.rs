@@ -0,0 +1,79 @@
+use crate::{
+    i18n::tr,
+    state::{
+        matrix::{MatrixRange, MatrixSetup, MatrixView, SortBy},
+        AppState,
+    },
+    widgets::{MatrixTable, Maybe},
+};
+use druid::{
+    lens,
+    widget::{CrossAxisAlignment, Flex, Label, RadioGroup, Scroll, TextBox},
+    Data, Widget, WidgetExt,
+};
+
+pub fn ui() -> impl Widget<AppState> {
+    Flex::column()
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .with_child(setup().lens(lens::Map::new(
+            |data: &AppState| data.matrix.clone(),
+            |data: &mut AppState, matrix: MatrixSetup| {
+                if !data.matrix.same(&matrix) {
+                    data.matrix = matrix;
+                    data.refresh_matrix();
+                }
+            },
+        )))
+        .with_spacer(10.0)
+        .with_flex_child(
+            Maybe::or_empty(
+                Flex::column()
+                    .cross_axis_alignment(CrossAxisAlignment::Start)
+                    .with_child(Label::dynamic(|view: &MatrixView, _| {
+                        view.error.clone().unwrap_or_default()
+                    }))
+                    .with_flex_child(Scroll::new(MatrixTable), 1.0),
+            )
+            .lens(AppState::matrix_view),
+            1.0,
+        )
+        .padding(10.0)
+}
+
+fn setup() -> impl Widget<MatrixSetup> {
+    Flex::row()
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .with_child(
+            RadioGroup::new(vec![
+                (tr("range-all"), MatrixRange::AllTime),
+                (tr("range-week"), MatrixRange::ThisWeek),
+                (tr("range-month"), MatrixRange::ThisMonth),
+                (tr("range-year"), MatrixRange::ThisYear),
+                (tr("range-custom"), MatrixRange::Custom),
+            ])
+            .lens(MatrixSetup::range),
+        )
+        .with_spacer(10.0)
+        .with_child(
+            Flex::column()
+                .cross_axis_alignment(CrossAxisAlignment::Start)
+                .with_child(Label::new(tr("range-from")))
+                .with_child(TextBox::new().lens(MatrixSetup::from))
+                .with_spacer(5.0)
+                .with_child(Label::new(tr("range-to")))
+                .with_child(TextBox::new().lens(MatrixSetup::to)),
+        )
+        .with_spacer(10.0)
+        .with_child(
+            Flex::column()
+                .cross_axis_alignment(CrossAxisAlignment::Start)
+                .with_child(Label::new(tr("sort-by")))
+                .with_child(
+                    RadioGroup::new(vec![
+                        (tr("sort-total"), SortBy::Total),
+                        (tr("sort-name"), SortBy::Name),
+                    ])
+                    .lens(MatrixSetup::sort_by),
+                ),
+        )
+}
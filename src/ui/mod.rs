@@ -1,5 +1,23 @@
 pub mod tracker;
 pub use tracker::ui as tracker;
 
-//mod insights;
-//pub use insights::ui as insights;
+pub mod settings;
+pub use settings::ui as settings;
+
+pub mod insights;
+pub use insights::ui as insights;
+
+pub mod calendar;
+pub use calendar::ui as calendar;
+
+pub mod matrix;
+pub use matrix::ui as matrix;
+
+pub mod manual;
+pub use manual::ui as manual;
+
+pub mod history;
+pub use history::ui as history;
+
+pub mod theme;
+pub use theme::themed;
@@ -0,0 +1,123 @@
+use crate::{
+    i18n::{self, tr, tr_args},
+    state::{AppState, DurationFormat, Settings},
+    ui::theme,
+};
+use druid::{
+    widget::{Checkbox, CrossAxisAlignment, Flex, Label, RadioGroup, Scroll, Stepper, TextBox},
+    Lens, Widget, WidgetExt,
+};
+
+pub fn ui() -> impl Widget<AppState> {
+    let settings = Flex::column()
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .with_child(setting(
+            "discard-threshold",
+            "unit-seconds",
+            (0.0, 3600.0, 5.0),
+            Settings::discard_threshold_secs,
+        ))
+        .with_spacer(5.0)
+        .with_child(setting(
+            "autosave-interval",
+            "unit-seconds",
+            (1.0, 600.0, 1.0),
+            Settings::autosave_interval_secs,
+        ))
+        .with_spacer(5.0)
+        .with_child(setting(
+            "tick-interval",
+            "unit-seconds",
+            (1.0, 60.0, 1.0),
+            Settings::tick_interval_secs,
+        ))
+        .with_spacer(5.0)
+        .with_child(setting(
+            "window-width",
+            "unit-pixels",
+            (200.0, 2000.0, 10.0),
+            Settings::window_width,
+        ))
+        .with_spacer(5.0)
+        .with_child(setting(
+            "window-height",
+            "unit-pixels",
+            (200.0, 2000.0, 10.0),
+            Settings::window_height,
+        ))
+        .with_spacer(10.0)
+        .with_child(Checkbox::new(tr("http-enabled")).lens(Settings::http_enabled))
+        .with_spacer(5.0)
+        .with_child(text_setting("http-address", Settings::http_address))
+        .with_spacer(5.0)
+        .with_child(text_setting("http-token", Settings::http_token))
+        .with_spacer(10.0)
+        .with_child(Label::new(tr("duration-format")))
+        .with_child(
+            RadioGroup::new(vec![
+                ("7h 15m 0s", DurationFormat::Units),
+                ("7:15", DurationFormat::Clock),
+                ("7.25h", DurationFormat::Decimal),
+            ])
+            .lens(Settings::duration_format),
+        )
+        .with_child(Checkbox::new(tr("setting-duration-days")).lens(Settings::duration_days))
+        .with_spacer(10.0)
+        .with_child(Label::new(tr("theme")))
+        .with_child(
+            RadioGroup::new(
+                theme::available()
+                    .into_iter()
+                    .map(|name| (name.clone(), name)),
+            )
+            .lens(Settings::theme),
+        )
+        .with_spacer(5.0)
+        .with_child(text_setting("accent-color", Settings::accent_color))
+        .with_spacer(10.0)
+        .with_child(Label::new(tr("language")))
+        .with_child(
+            // An empty id stands for the language of the system.
+            RadioGroup::new(
+                std::iter::once((tr("language-system"), String::new())).chain(
+                    i18n::LANGUAGES
+                        .iter()
+                        .map(|(name, id)| (name.to_string(), id.to_string())),
+                ),
+            )
+            .lens(Settings::language),
+        )
+        .with_spacer(10.0)
+        .with_child(Label::new(tr("applied-on-restart")))
+        .padding(10.0);
+    Scroll::new(settings).vertical().lens(AppState::settings)
+}
+
+fn setting(
+    title: &str,
+    unit: &'static str,
+    (min, max, step): (f64, f64, f64),
+    lens: impl Lens<Settings, f64> + 'static,
+) -> impl Widget<Settings> {
+    Flex::row()
+        .with_flex_child(Label::new(tr(title)).expand_width(), 1.0)
+        .with_child(Label::dynamic(move |value: &f64, _| {
+            tr_args(unit, &[("value", value.to_string().into())])
+        }))
+        .with_child(
+            Stepper::new()
+                .with_range(min, max)
+                .with_step(step)
+                .with_wraparound(false),
+        )
+        .lens(lens)
+        .expand_width()
+}
+
+fn text_setting(title: &str, lens: impl Lens<Settings, String> + 'static) -> impl Widget<Settings> {
+    Flex::row()
+        .with_child(Label::new(tr(title)))
+        .with_spacer(5.0)
+        .with_flex_child(TextBox::new().expand_width().lens(lens), 1.0)
+        .expand_width()
+}
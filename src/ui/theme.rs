@@ -0,0 +1,177 @@
+use crate::{
+    i18n,
+    state::{paths, AppState, Settings},
+};
+use druid::{theme, widget::EnvScope, Color, Env, Key, Widget, WidgetExt};
+use serde::Deserialize;
+use std::{cell::RefCell, fs};
+
+/// Highlights what the user is working on, like the busy days of the heatmap.
+pub const ACCENT: Key<Color> = Key::new("zeitig.theme.accent");
+
+/// Used when neither the settings nor the theme give a valid accent color.
+const DEFAULT_ACCENT: Color = Color::rgb8(0x2d, 0xa4, 0x4e);
+
+/// The colors of the user interface, each as `#rrggbb` or `#rrggbbaa`.
+///
+/// Themes are read from `themes/<name>.json` in the config directory,
+/// with missing colors taken from the dark theme.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    pub window_background: String,
+    pub text: String,
+    pub placeholder: String,
+    pub background_light: String,
+    pub background_dark: String,
+    pub border_light: String,
+    pub border_dark: String,
+    pub button_light: String,
+    pub button_dark: String,
+    pub selection: String,
+    pub accent: String,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::dark()
+    }
+}
+
+impl Theme {
+    pub fn light() -> Theme {
+        Theme {
+            window_background: "#f5f5f5".into(),
+            text: "#1e1e1e".into(),
+            placeholder: "#8a8a8a".into(),
+            background_light: "#ffffff".into(),
+            background_dark: "#e6e6e6".into(),
+            border_light: "#b4b4b4".into(),
+            border_dark: "#c8c8c8".into(),
+            button_light: "#fafafa".into(),
+            button_dark: "#dcdcdc".into(),
+            selection: "#cde3ff".into(),
+            accent: "#2da44e".into(),
+        }
+    }
+
+    pub fn dark() -> Theme {
+        Theme {
+            window_background: "#292929".into(),
+            text: "#f0f0ea".into(),
+            placeholder: "#808080".into(),
+            background_light: "#3a3a3a".into(),
+            background_dark: "#31312d".into(),
+            border_light: "#a1a1a1".into(),
+            border_dark: "#3a3a3a".into(),
+            button_light: "#595959".into(),
+            button_dark: "#212121".into(),
+            selection: "#3d5a80".into(),
+            accent: "#2da44e".into(),
+        }
+    }
+
+    /// The built-in theme of that name or the one from the themes directory,
+    /// falling back to the dark theme if it can not be read.
+    pub fn load(name: &str) -> Theme {
+        match name {
+            "light" => return Theme::light(),
+            "dark" => return Theme::dark(),
+            _ => {}
+        }
+        let path = paths::themes_dir().join(format!("{}.json", name));
+        let theme = fs::read_to_string(&path)
+            .map_err(|err| err.to_string())
+            .and_then(|text| serde_json::from_str(&text).map_err(|err| err.to_string()));
+        theme.unwrap_or_else(|err| {
+            log::error!("Could not read the theme {}: {}", path.display(), err);
+            Theme::dark()
+        })
+    }
+
+    /// Sets the colors of the theme, except for the ones which are invalid.
+    /// The accent is always set.
+    pub fn apply(&self, env: &mut Env, accent: &str) {
+        let colors = [
+            (theme::WINDOW_BACKGROUND_COLOR, &self.window_background),
+            (theme::LABEL_COLOR, &self.text),
+            (theme::PLACEHOLDER_COLOR, &self.placeholder),
+            (theme::BACKGROUND_LIGHT, &self.background_light),
+            (theme::BACKGROUND_DARK, &self.background_dark),
+            (theme::BORDER_LIGHT, &self.border_light),
+            (theme::BORDER_DARK, &self.border_dark),
+            (theme::BUTTON_LIGHT, &self.button_light),
+            (theme::BUTTON_DARK, &self.button_dark),
+            (theme::SELECTION_COLOR, &self.selection),
+        ];
+        for (key, hex) in colors.iter() {
+            if let Some(color) = parse(hex) {
+                env.set(key.clone(), color);
+            }
+        }
+        let accent = parse(accent)
+            .or_else(|| parse(&self.accent))
+            .unwrap_or(DEFAULT_ACCENT);
+        env.set(theme::PRIMARY_LIGHT, accent.clone());
+        env.set(theme::PRIMARY_DARK, accent.clone());
+        env.set(theme::CURSOR_COLOR, accent.clone());
+        env.set(ACCENT, accent);
+    }
+}
+
+fn parse(hex: &str) -> Option<Color> {
+    Color::from_hex_str(hex).ok()
+}
+
+/// The built-in themes followed by the ones in the themes directory.
+pub fn available() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(paths::themes_dir())
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "json" {
+                return None;
+            }
+            Some(path.file_stem()?.to_str()?.to_string())
+        })
+        .collect();
+    names.sort();
+    names.insert(0, "dark".to_string());
+    names.insert(0, "light".to_string());
+    names
+}
+
+/// Sets up the env of the whole application with the theme and the duration format
+/// chosen in the settings.
+pub fn configure_env(env: &mut Env, data: &AppState) {
+    Theme::load(&data.settings.theme).apply(env, &data.settings.accent_color);
+    i18n::set_duration_format(env, &data.settings);
+}
+
+/// Styles a window with the theme chosen in the settings and formats its durations
+/// as chosen there, following changes to them.
+///
+/// The window paints its background from the env of the application, which is only
+/// set up at the start, so the background is painted here again.
+pub fn themed(root: impl Widget<AppState> + 'static) -> impl Widget<AppState> {
+    // The theme is only read again when another one is chosen.
+    let loaded: RefCell<Option<(String, Theme)>> = RefCell::new(None);
+    EnvScope::new(
+        move |env, data: &AppState| {
+            let settings: &Settings = &data.settings;
+            let mut loaded = loaded.borrow_mut();
+            if loaded
+                .as_ref()
+                .map_or(true, |(name, _)| *name != settings.theme)
+            {
+                *loaded = Some((settings.theme.clone(), Theme::load(&settings.theme)));
+            }
+            if let Some((_, theme)) = loaded.as_ref() {
+                theme.apply(env, &settings.accent_color);
+            }
+            i18n::set_duration_format(env, settings);
+        },
+        root.background(theme::WINDOW_BACKGROUND_COLOR),
+    )
+}
@@ -0,0 +1,146 @@
+use crate::{
+    i18n::tr,
+    state::{AppState, Topic},
+};
+use druid::{keyboard_types::Key, Data, HotKey, Selector, SysMods};
+use im::Vector;
+use std::sync::Arc;
+
+/// Runs an entry of the command palette, no matter if it was chosen
+/// there or triggered by its shortcut.
+pub const RUN: Selector<PaletteEntry> = Selector::new("zeitig.commands.run");
+/// Moves the keyboard focus into the filter box of the palette.
+pub const FOCUS_PALETTE: Selector = Selector::new("zeitig.commands.focus_palette");
+
+#[derive(Clone, Data, PartialEq)]
+pub enum AppCommand {
+    ToggleSession,
+    TogglePause,
+    NewItem,
+    AddSession,
+    SwitchTopic,
+    TogglePinned,
+    Palette,
+    Insights,
+    Calendar,
+    History,
+    Table,
+    Settings,
+    SwitchTo(Topic),
+}
+
+/// The commands which are always available, in the order they are listed.
+static GLOBAL: [AppCommand; 12] = [
+    AppCommand::ToggleSession,
+    AppCommand::TogglePause,
+    AppCommand::NewItem,
+    AppCommand::AddSession,
+    AppCommand::SwitchTopic,
+    AppCommand::TogglePinned,
+    AppCommand::Palette,
+    AppCommand::Insights,
+    AppCommand::Calendar,
+    AppCommand::History,
+    AppCommand::Table,
+    AppCommand::Settings,
+];
+
+/// The prefix of all topic entries, so that typing it narrows the palette down to them.
+pub fn switch_prefix() -> String {
+    format!("{} ", tr("command-switch-to"))
+}
+
+impl AppCommand {
+    pub fn name(&self) -> String {
+        let id = match self {
+            AppCommand::ToggleSession => "command-toggle-session",
+            AppCommand::TogglePause => "command-toggle-pause",
+            AppCommand::NewItem => "command-new-item",
+            AppCommand::AddSession => "command-add-session",
+            AppCommand::SwitchTopic => "command-switch-topic",
+            AppCommand::TogglePinned => "command-toggle-pinned",
+            AppCommand::Palette => "command-palette",
+            AppCommand::Insights => "command-insights",
+            AppCommand::Calendar => "command-calendar",
+            AppCommand::History => "command-history",
+            AppCommand::Table => "command-table",
+            AppCommand::Settings => "command-settings",
+            AppCommand::SwitchTo(topic) => {
+                return format!(
+                    "{}{} {}",
+                    switch_prefix(),
+                    topic.action.as_ref(),
+                    topic.subject.as_ref()
+                )
+            }
+        };
+        tr(id)
+    }
+
+    /// The key combination triggering the command and how it is shown in the palette.
+    pub fn hotkey(&self) -> Option<(HotKey, &'static str)> {
+        let (key, label): (Key, _) = match self {
+            AppCommand::ToggleSession => (Key::Enter, "Ctrl+Enter"),
+            AppCommand::TogglePause => (Key::Character("b".into()), "Ctrl+B"),
+            AppCommand::NewItem => (Key::Character("n".into()), "Ctrl+N"),
+            AppCommand::AddSession => (Key::Character("e".into()), "Ctrl+E"),
+            AppCommand::SwitchTopic => (Key::Character("k".into()), "Ctrl+K"),
+            AppCommand::TogglePinned => (Key::Character("d".into()), "Ctrl+D"),
+            AppCommand::Palette => (Key::Character("p".into()), "Ctrl+P"),
+            AppCommand::Insights => (Key::Character("i".into()), "Ctrl+I"),
+            AppCommand::Calendar => (Key::Character("l".into()), "Ctrl+L"),
+            AppCommand::History => (Key::Character("h".into()), "Ctrl+H"),
+            AppCommand::Table => (Key::Character("t".into()), "Ctrl+T"),
+            AppCommand::Settings => (Key::Character(",".into()), "Ctrl+,"),
+            AppCommand::SwitchTo(_) => return None,
+        };
+        Some((HotKey::new(SysMods::Cmd, key), label))
+    }
+
+    pub fn entry(&self) -> PaletteEntry {
+        let label = match self.hotkey() {
+            Some((_, keys)) => format!("{}  ({})", self.name(), keys),
+            None => self.name(),
+        };
+        PaletteEntry {
+            label: label.into(),
+            command: self.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Data)]
+pub struct PaletteEntry {
+    pub label: Arc<str>,
+    pub command: AppCommand,
+}
+
+impl AsRef<str> for PaletteEntry {
+    fn as_ref(&self) -> &str {
+        &self.label
+    }
+}
+
+/// Every command with a shortcut, to be bound on the tracker window.
+pub fn shortcuts() -> impl Iterator<Item = (HotKey, PaletteEntry)> {
+    GLOBAL
+        .iter()
+        .filter_map(|command| Some((command.hotkey()?.0, command.entry())))
+}
+
+/// The global commands followed by one entry per topic,
+/// the ones with the most tracked time first.
+pub fn palette_entries(data: &AppState) -> Vector<PaletteEntry> {
+    let mut topics: Vec<_> = data.content.time_table.iter().collect();
+    topics.sort_by(|a, b| b.1.cmp(a.1));
+    GLOBAL
+        .iter()
+        .cloned()
+        .chain(
+            topics
+                .into_iter()
+                .map(|(topic, _)| AppCommand::SwitchTo(topic.clone())),
+        )
+        .map(|command| command.entry())
+        .collect()
+}
@@ -1,62 +1,187 @@
 use crate::{
     controller::{
         backend_msg, AutoSaver, BackendController, CloseController, CommandReceiver,
-        EnterController, Ticker,
+        EnterController, KeyController, RemoteController, SettingsSaver, Shortcuts, Ticker,
     },
+    i18n::{tr, tr_args, tr_duration},
     state::{
-        Action, ActiveSession, AppState, Content, Creating, Creator, DateTime, Session, Setup,
-        SpentTime, Subject, Topic,
+        fuzzy::{Candidate, ListFilter},
+        manual::ManualEntry,
+        matrix::MatrixView,
+        Action, ActiveSession, AppState, BackendError, Creating, Creator, Date, Insights,
+        QuickTopic, Setup, Subject, Topic,
     },
+    ui,
     widgets::Maybe,
 };
 use druid::{
+    keyboard_types::Key,
+    lens,
     widget::{
-        Button, CrossAxisAlignment, Flex, Label, List, MainAxisAlignment, Painter, Scroll,
-        SizedBox, TextBox,
+        Button, CrossAxisAlignment, Either, Flex, Label, LineBreaking, List, MainAxisAlignment,
+        Painter, Scroll, SizedBox, TextBox,
     },
-    Command, Data, EventCtx, LensExt, Selector, UnitPoint, Widget, WidgetExt,
+    Color, Command, Data, Env, EventCtx, Lens, LensExt, RenderContext, Selector, Widget, WidgetExt,
+    WindowDesc,
 };
-use std::time::Duration;
+use im::Vector;
+
+mod commands;
+use commands::AppCommand;
 
 const SELECT_ACTION: Selector<Action> = Selector::new("zeitig.select_action");
 const SELECT_SUBJECT: Selector<Subject> = Selector::new("zeitig.select_subject");
+const TOGGLE_PINNED: Selector<Topic> = Selector::new("zeitig.toggle_pinned");
 
-fn start_new_session(data: &mut AppState) {
-    data.active = Some(ActiveSession {
-        started: DateTime::now(),
-        duration: SpentTime::default(),
-    })
-}
+const ERROR_COLOR: Color = Color::rgb8(0xe0, 0x50, 0x50);
 
 pub fn end_session(ctx: &mut EventCtx, data: &mut AppState) {
-    if data.active.is_some() {
-        let active = data.active.take().unwrap();
-        if *active.duration > Duration::from_secs(30) {
-            let topic = Topic {
-                action: data.setup.selected_action.clone().unwrap(),
-                subject: data.setup.selected_subject.clone().unwrap(),
-            };
-            *data.content.time_table.get_mut(topic.clone()) += active.duration;
-            let session = Session {
-                topic,
-                started: active.started,
-                ended: DateTime::now(),
-            };
-            data.history.add(session.clone());
-            ctx.submit_command(backend_msg::ADD_SESSION.with(session));
-        }
+    if let Some(session) = data.end_session() {
+        ctx.submit_command(backend_msg::ADD_SESSION.with(session));
     }
 }
 
+/// Selects both parts of `topic`, ending the active session if the topic changes.
+pub fn select_topic(ctx: &mut EventCtx, data: &mut AppState, topic: Topic) {
+    let same_action = data.setup.selected_action.as_ref() == Some(&topic.action);
+    let same_subject = data.setup.selected_subject.as_ref() == Some(&topic.subject);
+    if !(same_action && same_subject) {
+        end_session(ctx, data);
+    }
+    data.setup.selected_action = Some(topic.action);
+    data.setup.selected_subject = Some(topic.subject);
+    data.refresh_heatmap();
+}
+
 fn handle_command(ctx: &mut EventCtx, data: &mut AppState, cmd: &Command) {
     if let Some(action) = cmd.get(SELECT_ACTION) {
         end_session(ctx, data);
         data.setup.selected_action = Some(action.clone());
+        data.refresh_heatmap();
     }
     if let Some(subject) = cmd.get(SELECT_SUBJECT) {
         end_session(ctx, data);
         data.setup.selected_subject = Some(subject.clone());
+        data.refresh_heatmap();
+    }
+    if let Some(topic) = cmd.get(TOGGLE_PINNED) {
+        let pinned = data.toggle_pinned(topic);
+        ctx.submit_command(backend_msg::SET_PINNED.with((topic.clone(), pinned)));
+    }
+    if let Some(entry) = cmd.get(commands::RUN) {
+        data.setup.palette = false;
+        run_command(ctx, data, &entry.command);
+    }
+}
+
+fn toggle_session(ctx: &mut EventCtx, data: &mut AppState) {
+    match data.active {
+        Some(_) => end_session(ctx, data),
+        None => data.start_session(),
+    }
+}
+
+fn toggle_creating(data: &mut Setup) {
+    data.creation_error.clear();
+    data.creating = match data.creating {
+        Creating::Nothing => Creating::Choosing,
+        _ => Creating::Nothing,
+    }
+}
+
+fn open_settings(ctx: &mut EventCtx) {
+    ctx.new_window(
+        WindowDesc::new(|| ui::themed(ui::settings()))
+            .title(tr("window-settings"))
+            .window_size((380.0, 520.0)),
+    );
+}
+
+fn open_insights(ctx: &mut EventCtx, data: &mut AppState) {
+    if data.insights.is_none() {
+        data.insights = Some(Insights::generate(&data.history));
+    }
+    if data.heatmap.is_none() {
+        data.heatmap = Some(data.heatmap_view());
     }
+    if data.chart_view.is_none() {
+        data.chart_view = Some(data.chart_view());
+    }
+    ctx.new_window(
+        WindowDesc::new(|| ui::themed(ui::insights()))
+            .title(tr("window-insights"))
+            .window_size((720.0, 700.0)),
+    );
+}
+
+fn open_calendar(ctx: &mut EventCtx, data: &mut AppState) {
+    let today = Date::from(*data.clock.now());
+    ui::calendar::open(ctx, data, today);
+}
+
+fn open_history(ctx: &mut EventCtx, data: &mut AppState) {
+    let today = Date::from(*data.clock.now());
+    ui::history::open(ctx, data, today);
+}
+
+fn open_table(ctx: &mut EventCtx, data: &mut AppState) {
+    if data.matrix_view.is_none() {
+        data.matrix_view = Some(MatrixView::generate(data));
+    }
+    ctx.new_window(
+        WindowDesc::new(|| ui::themed(ui::matrix()))
+            .title(tr("window-table"))
+            .window_size((600.0, 450.0)),
+    );
+}
+
+fn open_manual(ctx: &mut EventCtx, data: &mut AppState) {
+    data.manual = ManualEntry::new(data);
+    ctx.new_window(
+        WindowDesc::new(|| ui::themed(ui::manual()))
+            .title(tr("window-add-session"))
+            .window_size((320.0, 300.0)),
+    );
+}
+
+fn open_palette(ctx: &mut EventCtx, data: &mut AppState, text: &str) {
+    data.setup.palette = true;
+    data.setup.palette_filter.text = text.to_string();
+    data.setup.palette_filter.cursor = 0;
+    ctx.submit_command(commands::FOCUS_PALETTE);
+}
+
+fn run_command(ctx: &mut EventCtx, data: &mut AppState, command: &AppCommand) {
+    match command {
+        AppCommand::ToggleSession => toggle_session(ctx, data),
+        AppCommand::TogglePause => data.toggle_pause(),
+        AppCommand::NewItem => toggle_creating(&mut data.setup),
+        AppCommand::AddSession => open_manual(ctx, data),
+        AppCommand::SwitchTopic => open_palette(ctx, data, &commands::switch_prefix()),
+        AppCommand::Palette => open_palette(ctx, data, ""),
+        AppCommand::Insights => open_insights(ctx, data),
+        AppCommand::Calendar => open_calendar(ctx, data),
+        AppCommand::History => open_history(ctx, data),
+        AppCommand::Table => open_table(ctx, data),
+        AppCommand::Settings => open_settings(ctx),
+        AppCommand::TogglePinned => {
+            if let Some(topic) = data.selected_topic() {
+                ctx.submit_command(TOGGLE_PINNED.with(topic));
+            }
+        }
+        AppCommand::SwitchTo(topic) => {
+            select_topic(ctx, data, topic.clone());
+            if data.active.is_none() {
+                data.start_session();
+            }
+        }
+    }
+}
+
+fn shortcuts() -> Shortcuts {
+    commands::shortcuts().fold(Shortcuts::new(), |shortcuts, (hotkey, entry)| {
+        shortcuts.bind(hotkey, commands::RUN.with(entry))
+    })
 }
 
 pub fn ui() -> impl Widget<AppState> {
@@ -64,39 +189,54 @@ pub fn ui() -> impl Widget<AppState> {
         .with_child(header())
         .with_spacer(5.0)
         .with_child(separator())
-        .with_spacer(10.0)
-        .with_flex_child(lists(), 1.0)
+        .with_spacer(5.0)
+        .with_child(quick_switcher())
+        .with_spacer(5.0)
+        .with_flex_child(
+            Either::new(|data: &AppState, _| data.setup.palette, palette(), lists()),
+            1.0,
+        )
         .with_child(dialogs())
+        .with_child(overlap_notice())
+        .with_child(error_banner())
         .with_child(buttons())
         .controller(CommandReceiver::new(handle_command))
         .controller(BackendController::new())
         .controller(AutoSaver::new())
         .controller(CloseController::new())
+        .controller(SettingsSaver)
+        .controller(RemoteController)
+        .controller(shortcuts())
 }
 
 fn selected_action_label() -> impl Widget<Option<Action>> {
     Maybe::new(
         Label::dynamic(|action: &Action, _| format!("{}", action.as_ref())),
-        Label::new("No Action"),
+        Label::new(tr("no-action")),
     )
 }
 
 fn selected_subject_label() -> impl Widget<Option<Subject>> {
     Maybe::new(
         Label::dynamic(|subject: &Subject, _| format!("{}", subject.as_ref())),
-        Label::new("No Subject"),
+        Label::new(tr("no-subject")),
     )
 }
 
 fn session_duration_label() -> impl Widget<Option<ActiveSession>> {
     Maybe::new(
-        Label::dynamic(|session: &ActiveSession, _| format!("Session: {}", session.duration)),
-        Label::new("Session: not running"),
+        Label::dynamic(|session: &ActiveSession, env: &Env| {
+            if session.is_paused() {
+                tr_duration("session-paused", &session.duration, env)
+            } else {
+                tr_duration("session-running", &session.duration, env)
+            }
+        }),
+        Label::new(tr("session-stopped")),
     )
 }
 
 fn separator<T: Data>() -> impl Widget<T> {
-    use druid::RenderContext;
     Painter::new(|ctx, _, env| {
         let bounds = ctx.size().to_rect();
         ctx.fill(bounds, &env.get(druid::theme::BORDER_DARK));
@@ -113,24 +253,33 @@ fn header() -> impl Widget<AppState> {
                     Flex::column()
                         .cross_axis_alignment(CrossAxisAlignment::Start)
                         .with_child(session_duration_label().lens(AppState::active))
-                        .with_child(Label::dynamic(|data: &AppState, _| {
-                            format!("Total: {}", data.current_session_duration())
+                        .with_child(Label::dynamic(|data: &AppState, env: &Env| {
+                            let total = data.current_session_duration();
+                            if data.pending_commits > 0 {
+                                tr_duration("total-saving", &total, env)
+                            } else {
+                                tr_duration("total", &total, env)
+                            }
                         }))
                         .expand_width(),
                     1.0,
                 )
                 .with_spacer(5.0)
+                .with_child(Either::new(
+                    |data: &AppState, _| data.active.is_some(),
+                    Button::dynamic(|data: &AppState, _| {
+                        tr(if data.is_ticking() { "pause" } else { "resume" })
+                    })
+                    .on_click(|_, data: &mut AppState, _| data.toggle_pause()),
+                    SizedBox::empty(),
+                ))
+                .with_spacer(5.0)
                 .with_child(
                     Button::dynamic(|data: &AppState, _| match data.active {
-                        None => "Start".to_string(),
-                        Some(_) => "Stop".to_string(),
+                        None => tr("start"),
+                        Some(_) => tr("stop"),
                     })
-                    .on_click(|ctx, data: &mut AppState, _| {
-                        match data.active {
-                            Some(_) => end_session(ctx, data),
-                            None => start_new_session(data),
-                        }
-                    }),
+                    .on_click(|ctx, data: &mut AppState, _| toggle_session(ctx, data)),
                 )
                 .padding((10.0, 10.0, 10.0, 5.0))
                 .controller(Ticker::new()),
@@ -145,67 +294,228 @@ fn header() -> impl Widget<AppState> {
         )
 }
 
+/// The pinned and recently used topics, each starting a session with one click.
+fn quick_switcher() -> impl Widget<AppState> {
+    Scroll::new(
+        List::new(|| {
+            Flex::row()
+                .with_child(
+                    Button::dynamic(|quick: &QuickTopic, _| {
+                        format!(
+                            "{} {}",
+                            quick.topic.action.as_ref(),
+                            quick.topic.subject.as_ref()
+                        )
+                    })
+                    .on_click(|ctx, quick: &mut QuickTopic, _| {
+                        let entry = AppCommand::SwitchTo(quick.topic.clone()).entry();
+                        ctx.submit_command(commands::RUN.with(entry));
+                    }),
+                )
+                .with_child(
+                    Button::dynamic(|quick: &QuickTopic, _| {
+                        if quick.pinned { "★" } else { "☆" }.to_string()
+                    })
+                    .on_click(|ctx, quick: &mut QuickTopic, _| {
+                        ctx.submit_command(TOGGLE_PINNED.with(quick.topic.clone()));
+                    }),
+                )
+                .padding((0.0, 0.0, 6.0, 0.0))
+        })
+        .horizontal(),
+    )
+    .horizontal()
+    .padding((3.0, 0.0))
+    .lens(AppState::quick)
+}
+
 fn lists() -> impl Widget<AppState> {
     Flex::row()
         .cross_axis_alignment(CrossAxisAlignment::Start)
         .with_flex_child(
-            Scroll::new(List::new(|| {
-                Label::dynamic(|action: &Action, _| action.as_ref().to_string())
-                    .padding(3.0)
-                    .on_click(|ctx, action: &mut Action, _| {
-                        ctx.submit_command(SELECT_ACTION.with(action.clone()));
-                    })
-                    .align_horizontal(UnitPoint::CENTER)
-            }))
-            .vertical()
-            .lens(AppState::content.then(Content::actions))
-            .expand_width(),
+            filtered_list(
+                AppState::setup.then(Setup::action_filter),
+                |data| data.setup.action_filter.apply(&data.content.actions),
+                SELECT_ACTION,
+                None,
+            ),
             1.0,
         )
         .with_flex_child(
-            Scroll::new(List::new(|| {
-                Label::dynamic(|subject: &Subject, _| subject.as_ref().to_string())
+            filtered_list(
+                AppState::setup.then(Setup::subject_filter),
+                |data| data.setup.subject_filter.apply(&data.content.subjects),
+                SELECT_SUBJECT,
+                None,
+            ),
+            1.0,
+        )
+        .expand_height()
+}
+
+/// The command palette, replacing the topic lists while it is open.
+/// Escape closes it again.
+fn palette() -> impl Widget<AppState> {
+    filtered_list(
+        AppState::setup.then(Setup::palette_filter),
+        |data| {
+            let entries = commands::palette_entries(data);
+            data.setup.palette_filter.apply(&entries)
+        },
+        commands::RUN,
+        Some(commands::FOCUS_PALETTE),
+    )
+    .controller(KeyController::new(|_, data: &mut AppState, key| {
+        if key == &Key::Escape {
+            data.setup.palette = false;
+            data.setup.palette_filter.clear();
+            true
+        } else {
+            false
+        }
+    }))
+}
+
+/// A list with a filter box above it, which can be navigated with the arrow keys
+/// while the box is focused. Enter selects the highlighted item.
+///
+/// The box takes the focus when `focus` is submitted.
+fn filtered_list<T: Data + AsRef<str>>(
+    filter: impl Lens<AppState, ListFilter> + Clone + 'static,
+    candidates: fn(&AppState) -> Vector<Candidate<T>>,
+    select: Selector<T>,
+    focus: Option<Selector>,
+) -> impl Widget<AppState> {
+    let navigation = {
+        let filter = filter.clone();
+        KeyController::new(move |ctx, data: &mut AppState, key| {
+            let candidates = candidates(data);
+            filter.with_mut(data, |filter| match key {
+                Key::ArrowDown => {
+                    filter.move_cursor(1, candidates.len());
+                    true
+                }
+                Key::ArrowUp => {
+                    filter.move_cursor(-1, candidates.len());
+                    true
+                }
+                Key::Enter => {
+                    if let Some(candidate) = candidates.iter().find(|c| c.highlighted) {
+                        ctx.submit_command(select.with(candidate.item.clone()));
+                    }
+                    filter.clear();
+                    true
+                }
+                Key::Escape => {
+                    filter.clear();
+                    true
+                }
+                _ => {
+                    filter.cursor = 0;
+                    false
+                }
+            })
+        })
+    };
+
+    Flex::column()
+        .with_child(
+            TextBox::new()
+                .with_placeholder(tr("filter"))
+                .expand_width()
+                .lens(filter.then(ListFilter::text))
+                .controller(navigation)
+                .controller(CommandReceiver::new(move |ctx, _, cmd| {
+                    if focus.map_or(false, |focus| cmd.is(focus)) {
+                        ctx.request_focus();
+                    }
+                })),
+        )
+        .with_spacer(3.0)
+        .with_flex_child(
+            Scroll::new(List::new(move || {
+                Label::dynamic(|c: &Candidate<T>, _| c.item.as_ref().to_string())
                     .padding(3.0)
-                    .on_click(|ctx, subject: &mut Subject, _| {
-                        ctx.submit_command(SELECT_SUBJECT.with(subject.clone()));
+                    .center()
+                    .expand_width()
+                    .background(Painter::new(|ctx, c: &Candidate<T>, env| {
+                        if c.highlighted {
+                            let bounds = ctx.size().to_rect();
+                            ctx.fill(bounds, &env.get(druid::theme::SELECTION_COLOR));
+                        }
+                    }))
+                    .on_click(move |ctx, c: &mut Candidate<T>, _| {
+                        ctx.submit_command(select.with(c.item.clone()));
                     })
-                    .align_horizontal(UnitPoint::CENTER)
             }))
             .vertical()
-            .lens(AppState::content.then(Content::subjects))
-            .expand_width(),
+            .lens(lens::Map::new(candidates, |_: &mut AppState, _| {})),
             1.0,
         )
-        .expand_height()
+        .padding((3.0, 0.0))
+        .expand_width()
 }
 
 fn dialogs() -> impl Widget<AppState> {
     const ADVANCE: Selector<Creating> = Selector::new("zeitig.dialogs.advance");
     fn handle_advance(ctx: &mut EventCtx, data: &mut AppState, cmd: &Command) {
         if let Some(creating) = cmd.get(ADVANCE) {
+            data.setup.creation_error.clear();
             if creating == &Creating::Nothing {
+                // The dialog stays open until the backend confirms the new item.
                 match &data.setup.creating {
-                    Creating::Action(a) => {
-                        ctx.submit_command(backend_msg::ADD_ACTION.with(a.to_owned()));
-                    }
-                    Creating::Subject(s) => {
-                        ctx.submit_command(backend_msg::ADD_SUBJECT.with(s.to_owned()));
-                    }
-                    _ => {}
+                    Creating::Action(a) => match validate_name(
+                        a,
+                        data.content.find_action_by_name(a.trim()).is_some(),
+                        ("action-needs-name", "action-exists"),
+                    ) {
+                        Ok(()) => {
+                            ctx.submit_command(backend_msg::ADD_ACTION.with(a.trim().to_owned()))
+                        }
+                        Err(message) => data.setup.creation_error = message,
+                    },
+                    Creating::Subject(s) => match validate_name(
+                        s,
+                        data.content.find_subject_by_name(s.trim()).is_some(),
+                        ("subject-needs-name", "subject-exists"),
+                    ) {
+                        Ok(()) => {
+                            ctx.submit_command(backend_msg::ADD_SUBJECT.with(s.trim().to_owned()))
+                        }
+                        Err(message) => data.setup.creation_error = message,
+                    },
+                    _ => data.setup.creating = Creating::Nothing,
                 }
+                return;
             }
             data.setup.creating = creating.clone();
         }
     }
+    /// Checks `name`, with the ids of the messages for an empty and a taken name.
+    fn validate_name(name: &str, exists: bool, (empty, taken): (&str, &str)) -> Result<(), String> {
+        if name.trim().is_empty() {
+            Err(tr(empty))
+        } else if exists {
+            Err(tr_args(taken, &[("name", name.trim().to_string().into())]))
+        } else {
+            Ok(())
+        }
+    }
     fn handle_creation(_ctx: &mut EventCtx, data: &mut AppState, cmd: &Command) {
         match cmd {
             _ if cmd.is(backend_msg::ACTION_ADDED) => {
                 let action = cmd.get_unchecked(backend_msg::ACTION_ADDED).clone();
                 data.content.actions.insert_ord(action);
+                data.setup.creating = Creating::Nothing;
             }
             _ if cmd.is(backend_msg::SUBJECT_ADDED) => {
                 let subject = cmd.get_unchecked(backend_msg::SUBJECT_ADDED).clone();
                 data.content.subjects.insert_ord(subject);
+                data.setup.creating = Creating::Nothing;
+            }
+            _ if cmd.is(backend_msg::CREATION_FAILED) => {
+                let message = cmd.get_unchecked(backend_msg::CREATION_FAILED);
+                data.setup.creation_error = message.clone();
             }
             _ => {}
         }
@@ -213,7 +523,7 @@ fn dialogs() -> impl Widget<AppState> {
     fn finish(ctx: &mut EventCtx) {
         ctx.submit_command(ADVANCE.with(Creating::Nothing));
     }
-    fn base<T: Data>(title: &str, content: impl Widget<T> + 'static) -> impl Widget<T> {
+    fn base<T: Data>(title: String, content: impl Widget<T> + 'static) -> impl Widget<T> {
         Flex::column()
             .with_child(Label::new(title))
             .with_spacer(5.0)
@@ -223,21 +533,21 @@ fn dialogs() -> impl Widget<AppState> {
             .rounded(5.0)
             .padding(10.0)
     }
-    Creator::new()
+    let creator = Creator::new()
         .nothing(SizedBox::empty())
         .choosing(base(
-            "What to add?",
+            tr("what-to-add"),
             Flex::row()
-                .with_child(Button::new("Action").on_click(|ctx, _, _| {
+                .with_child(Button::new(tr("action")).on_click(|ctx, _, _| {
                     ctx.submit_command(ADVANCE.with(Creating::Action(String::new())))
                 }))
                 .with_spacer(5.0)
-                .with_child(Button::new("Subject").on_click(|ctx, _, _| {
+                .with_child(Button::new(tr("subject")).on_click(|ctx, _, _| {
                     ctx.submit_command(ADVANCE.with(Creating::Subject(String::new())))
                 })),
         ))
         .action(base(
-            "Add new action:",
+            tr("add-action"),
             Flex::row()
                 .with_flex_child(
                     TextBox::new()
@@ -246,10 +556,10 @@ fn dialogs() -> impl Widget<AppState> {
                     1.0,
                 )
                 .with_spacer(3.0)
-                .with_child(Button::new("Add").on_click(|ctx, _, _| finish(ctx))),
+                .with_child(Button::new(tr("add")).on_click(|ctx, _, _| finish(ctx))),
         ))
         .subject(base(
-            "Add new subject:",
+            tr("add-subject"),
             Flex::row()
                 .with_flex_child(
                     TextBox::new()
@@ -258,36 +568,115 @@ fn dialogs() -> impl Widget<AppState> {
                     1.0,
                 )
                 .with_spacer(3.0)
-                .with_child(Button::new("Add").on_click(|ctx, _, _| finish(ctx))),
+                .with_child(Button::new(tr("add")).on_click(|ctx, _, _| finish(ctx))),
+        ))
+        .lens(Setup::creating);
+    Flex::column()
+        .with_child(creator)
+        .with_child(Either::new(
+            |setup: &Setup, _| setup.creation_error.is_empty(),
+            SizedBox::empty(),
+            Label::dynamic(|setup: &Setup, _| setup.creation_error.clone())
+                .with_text_color(ERROR_COLOR)
+                .padding((10.0, 0.0, 10.0, 5.0)),
         ))
-        .lens(AppState::setup.then(Setup::creating))
+        .lens(AppState::setup)
         .controller(CommandReceiver::new(handle_advance))
         .controller(CommandReceiver::new(handle_creation))
 }
 
+/// The writes to the backend that failed, each of which can be retried or dismissed.
+fn error_banner() -> impl Widget<AppState> {
+    List::new(|| {
+        Flex::row()
+            .with_flex_child(
+                Label::dynamic(|error: &BackendError, _| error.message.clone())
+                    .with_text_color(ERROR_COLOR)
+                    .with_line_break_mode(LineBreaking::WordWrap)
+                    .expand_width(),
+                1.0,
+            )
+            .with_child(
+                Button::new(tr("retry")).on_click(|ctx, error: &mut BackendError, _| {
+                    ctx.submit_command(backend_msg::RETRY.with(error.id))
+                }),
+            )
+            .with_child(
+                Button::new(tr("dismiss")).on_click(|ctx, error: &mut BackendError, _| {
+                    ctx.submit_command(backend_msg::DISMISS.with(error.id))
+                }),
+            )
+            .padding((5.0, 3.0))
+    })
+    .lens(AppState::backend_errors)
+}
+
+/// Points out overlapping sessions, which would be counted twice.
+fn overlap_notice() -> impl Widget<AppState> {
+    Either::new(
+        |data: &AppState, _| data.overlaps > 0,
+        Flex::row()
+            .with_flex_child(
+                Label::dynamic(|data: &AppState, _| {
+                    tr_args("overlaps", &[("count", data.overlaps.into())])
+                })
+                .expand_width(),
+                1.0,
+            )
+            .with_child(
+                Button::new(tr("resolve"))
+                    .on_click(|ctx, data: &mut AppState, _| ui::history::open_overlaps(ctx, data)),
+            )
+            .padding((5.0, 3.0)),
+        SizedBox::empty(),
+    )
+}
+
 fn buttons() -> impl Widget<AppState> {
-    Flex::row()
+    let setup = Flex::row()
         .with_flex_child(
             Button::dynamic(Setup::new_item_label)
-                .on_click(|_, data: &mut Setup, _| {
-                    data.creating = match data.creating {
-                        Creating::Nothing => Creating::Choosing,
-                        _ => Creating::Nothing,
-                    }
-                })
+                .on_click(|_, data: &mut Setup, _| toggle_creating(data))
                 .lens(AppState::setup)
                 .expand_width(),
             1.0,
         )
         .with_flex_child(
-            Button::new("Insights")
-                .on_click(|_ctx, _data: &mut AppState, _| {
-                    //if data.insights.is_none() {
-                    //    data.insights = Some(Insights::generate(data));
-                    //}
-                    //ctx.new_window(WindowDesc::new(ui::insights).title("Insights"));
-                })
+            Button::new(tr("add-session"))
+                .on_click(|ctx, data: &mut AppState, _| open_manual(ctx, data))
                 .expand_width(),
             1.0,
         )
+        .with_flex_child(
+            Button::new(tr("settings"))
+                .on_click(|ctx, _data: &mut AppState, _| open_settings(ctx))
+                .expand_width(),
+            1.0,
+        );
+    let views = Flex::row()
+        .with_flex_child(
+            Button::new(tr("insights"))
+                .on_click(|ctx, data: &mut AppState, _| open_insights(ctx, data))
+                .expand_width(),
+            1.0,
+        )
+        .with_flex_child(
+            Button::new(tr("calendar"))
+                .on_click(|ctx, data: &mut AppState, _| open_calendar(ctx, data))
+                .expand_width(),
+            1.0,
+        )
+        .with_flex_child(
+            Button::new(tr("history"))
+                .on_click(|ctx, data: &mut AppState, _| open_history(ctx, data))
+                .expand_width(),
+            1.0,
+        )
+        .with_flex_child(
+            Button::new(tr("table"))
+                .on_click(|ctx, data: &mut AppState, _| open_table(ctx, data))
+                .expand_width(),
+            1.0,
+        );
+    Flex::column().with_child(setup).with_child(views)
 }
@@ -0,0 +1,100 @@
+use crate::{
+    i18n,
+    state::{aggregate::ChartView, SpentTime},
+    widgets::paint::{draw_label, item_color},
+};
+use druid::{widget::prelude::*, Data, Rect, RenderContext};
+use std::collections::BTreeMap;
+
+const AXIS_GUTTER: f64 = 40.0;
+const LABEL_HEIGHT: f64 = 16.0;
+const LEGEND_HEIGHT: f64 = 18.0;
+const BAR_GAP: f64 = 4.0;
+
+/// Bars of tracked time per period, stacked by their segments.
+pub struct BarChart;
+
+impl Widget<ChartView> for BarChart {
+    fn event(&mut self, _ctx: &mut EventCtx, _event: &Event, _data: &mut ChartView, _env: &Env) {}
+
+    fn lifecycle(
+        &mut self,
+        _ctx: &mut LifeCycleCtx,
+        _event: &LifeCycle,
+        _data: &ChartView,
+        _env: &Env,
+    ) {
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &ChartView, data: &ChartView, _env: &Env) {
+        if !old_data.same(data) || ctx.env_changed() {
+            ctx.request_paint();
+        }
+    }
+
+    fn layout(
+        &mut self,
+        _ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &ChartView,
+        _env: &Env,
+    ) -> Size {
+        bc.constrain((500.0, 220.0))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &ChartView, env: &Env) {
+        let size = ctx.size();
+        let text_color = env.get(druid::theme::LABEL_COLOR);
+        let plot_height = size.height - LABEL_HEIGHT - LEGEND_HEIGHT;
+        let max = data
+            .bars
+            .iter()
+            .map(|bar| bar.total())
+            .max()
+            .unwrap_or_default()
+            .as_secs_f64()
+            .max(1.0);
+        draw_label(
+            ctx,
+            &i18n::duration(
+                &SpentTime::from(std::time::Duration::from_secs_f64(max)),
+                env,
+            ),
+            (0.0, 0.0),
+            10.0,
+            &text_color,
+        );
+
+        let count = data.bars.len().max(1) as f64;
+        let width = (size.width - AXIS_GUTTER) / count;
+        let mut legend = BTreeMap::new();
+        for (index, bar) in data.bars.iter().enumerate() {
+            let x = AXIS_GUTTER + index as f64 * width;
+            let mut bottom = plot_height;
+            for segment in &bar.segments {
+                let height = segment.spent_time.as_secs_f64() / max * plot_height;
+                let rect = Rect::new(
+                    x + BAR_GAP / 2.0,
+                    bottom - height,
+                    x + width - BAR_GAP / 2.0,
+                    bottom,
+                );
+                ctx.fill(rect, &item_color(segment.id));
+                bottom -= height;
+                legend.insert(segment.name.clone(), segment.id);
+            }
+            draw_label(ctx, &bar.label, (x, plot_height + 2.0), 10.0, &text_color);
+        }
+
+        let mut x = AXIS_GUTTER;
+        let y = size.height - LEGEND_HEIGHT + 4.0;
+        for (name, id) in legend {
+            ctx.fill(Rect::new(x, y + 2.0, x + 8.0, y + 10.0), &item_color(id));
+            draw_label(ctx, &name, (x + 11.0, y), 10.0, &text_color);
+            x += 16.0 + name.chars().count() as f64 * 6.0;
+            if x > size.width {
+                break;
+            }
+        }
+    }
+}
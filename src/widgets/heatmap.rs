@@ -0,0 +1,106 @@
+use crate::{
+    i18n,
+    state::{aggregate::HeatmapView, Date},
+    ui::theme::ACCENT,
+    widgets::paint::draw_label,
+};
+use druid::{widget::prelude::*, Data, Rect, RenderContext, Selector};
+
+/// Submitted with the day whose cell has been clicked.
+pub const DAY_CLICKED: Selector<Date> = Selector::new("zeitig.heatmap.day-clicked");
+
+const CELL: f64 = 11.0;
+const GAP: f64 = 2.0;
+const MONTH_HEADER: f64 = 14.0;
+
+/// A grid with one cell per day and one column per week,
+/// shaded by the time spent on that day.
+pub struct Heatmap;
+
+impl Heatmap {
+    fn cell(column: i64, row: i64) -> Rect {
+        let x = column as f64 * (CELL + GAP);
+        let y = MONTH_HEADER + row as f64 * (CELL + GAP);
+        Rect::new(x, y, x + CELL, y + CELL)
+    }
+
+    fn weeks(data: &HeatmapView) -> i64 {
+        (*data.last_day - *data.first_day).whole_days() / 7 + 1
+    }
+}
+
+impl Widget<HeatmapView> for Heatmap {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut HeatmapView, _env: &Env) {
+        if let Event::MouseDown(mouse) = event {
+            let column = (mouse.pos.x / (CELL + GAP)).floor() as i64;
+            let row = ((mouse.pos.y - MONTH_HEADER) / (CELL + GAP)).floor() as i64;
+            if (0..Self::weeks(data)).contains(&column) && (0..7).contains(&row) {
+                let day = data.first_day.add_days(column * 7 + row);
+                if *day <= *data.last_day {
+                    ctx.submit_command(DAY_CLICKED.with(day));
+                }
+            }
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        _ctx: &mut LifeCycleCtx,
+        _event: &LifeCycle,
+        _data: &HeatmapView,
+        _env: &Env,
+    ) {
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &HeatmapView,
+        data: &HeatmapView,
+        _env: &Env,
+    ) {
+        if !old_data.same(data) || ctx.env_changed() {
+            ctx.request_paint();
+        }
+    }
+
+    fn layout(
+        &mut self,
+        _ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &HeatmapView,
+        _env: &Env,
+    ) -> Size {
+        let width = Self::weeks(data) as f64 * (CELL + GAP);
+        let height = MONTH_HEADER + 7.0 * (CELL + GAP);
+        bc.constrain((width, height))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &HeatmapView, env: &Env) {
+        let empty = env.get(druid::theme::BACKGROUND_LIGHT);
+        let text_color = env.get(druid::theme::LABEL_COLOR);
+        let max = data.max.as_secs_f64().max(1.0);
+
+        for column in 0..Self::weeks(data) {
+            let monday = data.first_day.add_days(column * 7);
+            if monday.day() <= 7 {
+                let x = column as f64 * (CELL + GAP);
+                draw_label(ctx, &i18n::month(&monday), (x, 0.0), 10.0, &text_color);
+            }
+            for row in 0..7 {
+                let day = monday.add_days(row);
+                if *day > *data.last_day {
+                    break;
+                }
+                let spent = data.get(&day).as_secs_f64();
+                let cell = Self::cell(column, row);
+                if spent > 0.0 {
+                    let level = (spent / max * 4.0).ceil() / 4.0;
+                    ctx.fill(cell, &env.get(ACCENT).with_alpha(level));
+                } else {
+                    ctx.fill(cell, &empty);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{
+    i18n::{self, tr},
+    state::{matrix::MatrixView, SpentTime},
+    widgets::paint::draw_label,
+};
+use druid::{kurbo::Line, widget::prelude::*, Color, Data, RenderContext};
+
+const CELL_WIDTH: f64 = 90.0;
+const CELL_HEIGHT: f64 = 20.0;
+const MAX_NAME: usize = 12;
+
+/// A table of the time spent per action and subject, with totals
+/// in the last row and column.
+pub struct MatrixTable;
+
+fn shorten(name: &str) -> String {
+    if name.chars().count() > MAX_NAME {
+        let short: String = name.chars().take(MAX_NAME - 1).collect();
+        format!("{}…", short)
+    } else {
+        name.to_string()
+    }
+}
+
+fn time_label(time: SpentTime, env: &Env) -> String {
+    if time.as_secs() == 0 {
+        String::from("-")
+    } else {
+        i18n::duration(&time, env)
+    }
+}
+
+fn cell(ctx: &mut PaintCtx, column: usize, row: usize, text: &str, color: &Color) {
+    let origin = (
+        column as f64 * CELL_WIDTH + 3.0,
+        row as f64 * CELL_HEIGHT + 3.0,
+    );
+    draw_label(ctx, text, origin, 11.0, color);
+}
+
+impl Widget<MatrixView> for MatrixTable {
+    fn event(&mut self, _ctx: &mut EventCtx, _event: &Event, _data: &mut MatrixView, _env: &Env) {}
+
+    fn lifecycle(
+        &mut self,
+        _ctx: &mut LifeCycleCtx,
+        _event: &LifeCycle,
+        _data: &MatrixView,
+        _env: &Env,
+    ) {
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &MatrixView,
+        data: &MatrixView,
+        _env: &Env,
+    ) {
+        if !old_data.same(data) || ctx.env_changed() {
+            ctx.request_layout();
+            ctx.request_paint();
+        }
+    }
+
+    fn layout(
+        &mut self,
+        _ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &MatrixView,
+        _env: &Env,
+    ) -> Size {
+        let columns = data.subjects.len() + 2;
+        let rows = data.actions.len() + 2;
+        bc.constrain((columns as f64 * CELL_WIDTH, rows as f64 * CELL_HEIGHT))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &MatrixView, env: &Env) {
+        let size = ctx.size();
+        let text_color = env.get(druid::theme::LABEL_COLOR);
+        let line_color = env.get(druid::theme::BORDER_DARK);
+
+        let total_column = data.subjects.len() + 1;
+        let total_row = data.actions.len() + 1;
+        for (column, (subject, time)) in data.subjects.iter().enumerate() {
+            cell(ctx, column + 1, 0, &shorten(subject.as_ref()), &text_color);
+            cell(
+                ctx,
+                column + 1,
+                total_row,
+                &time_label(*time, env),
+                &text_color,
+            );
+        }
+        for (row, (action, time)) in data.actions.iter().enumerate() {
+            cell(ctx, 0, row + 1, &shorten(action.as_ref()), &text_color);
+            for (column, (subject, _)) in data.subjects.iter().enumerate() {
+                let time = data.get(action, subject);
+                cell(
+                    ctx,
+                    column + 1,
+                    row + 1,
+                    &time_label(time, env),
+                    &text_color,
+                );
+            }
+            cell(
+                ctx,
+                total_column,
+                row + 1,
+                &time_label(*time, env),
+                &text_color,
+            );
+        }
+        let total = tr("table-total");
+        cell(ctx, total_column, 0, &total, &text_color);
+        cell(ctx, 0, total_row, &total, &text_color);
+        cell(
+            ctx,
+            total_column,
+            total_row,
+            &time_label(data.total, env),
+            &text_color,
+        );
+
+        let x = CELL_WIDTH;
+        ctx.stroke(Line::new((x, 0.0), (x, size.height)), &line_color, 1.0);
+        let x = total_column as f64 * CELL_WIDTH;
+        ctx.stroke(Line::new((x, 0.0), (x, size.height)), &line_color, 1.0);
+        let y = CELL_HEIGHT;
+        ctx.stroke(Line::new((0.0, y), (size.width, y)), &line_color, 1.0);
+        let y = total_row as f64 * CELL_HEIGHT;
+        ctx.stroke(Line::new((0.0, y), (size.width, y)), &line_color, 1.0);
+    }
+}
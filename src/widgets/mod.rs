@@ -1,2 +1,16 @@
 mod maybe;
 pub use maybe::Maybe;
+
+pub mod paint;
+
+mod bar_chart;
+pub use bar_chart::BarChart;
+
+mod matrix_table;
+pub use matrix_table::MatrixTable;
+
+pub mod heatmap;
+pub use heatmap::Heatmap;
+
+mod week_grid;
+pub use week_grid::WeekGrid;
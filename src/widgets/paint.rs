@@ -0,0 +1,48 @@
+use druid::{
+    piet::{FontFamily, Text, TextLayoutBuilder},
+    Color, PaintCtx, Point, RenderContext,
+};
+
+/// A distinct color for the item with the given id.
+///
+/// Neighbouring ids are spread around the color wheel by the golden angle.
+pub fn item_color(id: usize) -> Color {
+    let hue = (id as f64 * 137.508) % 360.0;
+    hsl(hue, 0.55, 0.55)
+}
+
+fn hsl(hue: f64, saturation: f64, lightness: f64) -> Color {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let sector = hue / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    Color::rgb(r + m, g + m, b + m)
+}
+
+/// Draws a single line of text with its top left corner at `origin`.
+pub fn draw_label(
+    ctx: &mut PaintCtx,
+    text: &str,
+    origin: impl Into<Point>,
+    size: f64,
+    color: &Color,
+) {
+    let layout = ctx
+        .text()
+        .new_text_layout(text.to_string())
+        .font(FontFamily::SYSTEM_UI, size)
+        .text_color(color.clone())
+        .build();
+    match layout {
+        Ok(layout) => ctx.draw_text(&layout, origin),
+        Err(err) => log::warn!("Could not lay out '{}': {}", text, err),
+    }
+}
@@ -0,0 +1,109 @@
+use crate::{
+    i18n,
+    state::{calendar::WeekView, DateTime, SpentTime},
+    widgets::paint::{draw_label, item_color},
+};
+use druid::{kurbo::Line, widget::prelude::*, Color, Data, Point, Rect, RenderContext};
+
+const HOUR_GUTTER: f64 = 36.0;
+const DAY_HEADER: f64 = 20.0;
+const SECONDS_PER_DAY: f64 = 24.0 * 60.0 * 60.0;
+
+/// Draws the sessions of one week on a grid of days and hours.
+pub struct WeekGrid;
+
+impl WeekGrid {
+    fn column_width(size: Size) -> f64 {
+        (size.width - HOUR_GUTTER) / 7.0
+    }
+
+    fn hour_height(size: Size) -> f64 {
+        (size.height - DAY_HEADER) / 24.0
+    }
+
+    /// The vertical position of `dt` within its day.
+    fn offset_in_day(dt: &DateTime, size: Size) -> f64 {
+        let secs = SpentTime::between(&dt.start_of_day(), dt).as_secs_f64();
+        DAY_HEADER + secs / SECONDS_PER_DAY * (size.height - DAY_HEADER)
+    }
+}
+
+impl Widget<WeekView> for WeekGrid {
+    fn event(&mut self, _ctx: &mut EventCtx, _event: &Event, _data: &mut WeekView, _env: &Env) {}
+
+    fn lifecycle(
+        &mut self,
+        _ctx: &mut LifeCycleCtx,
+        _event: &LifeCycle,
+        _data: &WeekView,
+        _env: &Env,
+    ) {
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &WeekView, data: &WeekView, _env: &Env) {
+        if !old_data.same(data) || ctx.env_changed() {
+            ctx.request_paint();
+        }
+    }
+
+    fn layout(
+        &mut self,
+        _ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &WeekView,
+        _env: &Env,
+    ) -> Size {
+        bc.constrain((600.0, 24.0 * 24.0 + DAY_HEADER))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &WeekView, env: &Env) {
+        let size = ctx.size();
+        let column = Self::column_width(size);
+        let hour = Self::hour_height(size);
+        let text_color = env.get(druid::theme::LABEL_COLOR);
+        let line_color = env.get(druid::theme::BORDER_DARK);
+
+        for h in 0..24 {
+            let y = DAY_HEADER + h as f64 * hour;
+            ctx.stroke(
+                Line::new((HOUR_GUTTER, y), (size.width, y)),
+                &line_color,
+                1.0,
+            );
+            if h % 3 == 0 {
+                draw_label(ctx, &format!("{:02}:00", h), (2.0, y), 10.0, &text_color);
+            }
+        }
+        for d in 0..7 {
+            let x = HOUR_GUTTER + d as f64 * column;
+            ctx.stroke(Line::new((x, 0.0), (x, size.height)), &line_color, 1.0);
+            let day = data.begin.add_days(d);
+            let title = format!("{} {}", i18n::short_weekday(&day), i18n::short_date(&day));
+            draw_label(ctx, &title, (x + 3.0, 3.0), 11.0, &text_color);
+        }
+
+        for session in &data.sessions {
+            let (id, name) = data.color_by.key(&session.topic);
+            let color = item_color(id);
+            for (start, end) in session.split_days() {
+                let day = (start.date() - *data.begin).whole_days();
+                if day < 0 || day >= 7 {
+                    continue;
+                }
+                let x = HOUR_GUTTER + day as f64 * column;
+                let top = Self::offset_in_day(&start, size);
+                let bottom = if *end == *end.start_of_day() {
+                    size.height
+                } else {
+                    Self::offset_in_day(&end, size)
+                };
+                let block = Rect::new(x + 1.0, top, x + column - 1.0, bottom.max(top + 1.0));
+                ctx.fill(block, &color);
+                if block.height() > 14.0 {
+                    let origin = Point::new(block.x0 + 2.0, block.y0 + 1.0);
+                    draw_label(ctx, name, origin, 10.0, &Color::BLACK);
+                }
+            }
+        }
+    }
+}